@@ -7,6 +7,34 @@ struct OverlayConfig {
     len: i32,
     colors: mat4x4<f32>,
     dts: vec4<f32>,
+    budget_line_count: u32,
+    budget_line_dts: vec4<f32>,
+    budget_line_colors: mat4x4<f32>,
+    budget_line_labels: vec4<u32>,
+    scale_mode: u32,
+    // 0 when the width of the bars depends on their delta time
+    bar_count: u32,
+    graph_style: u32,
+    antialiasing: u32,
+    series_count: u32,
+    series_colors: mat4x4<f32>,
+    pacing_tolerance: f32,
+    row_count: u32,
+    // The kind of each row, packed by 4
+    rows: array<vec4<u32>, 4>,
+    background: vec4<f32>,
+    text_color: vec4<f32>,
+    // The alpha is 0 when the shadow is disabled
+    shadow_color: vec4<f32>,
+    padding: f32,
+    corner_radius: f32,
+    // 0 when the hatching is disabled
+    hatch_threshold: f32,
+    // 0 when the coverage of the glyphs is read from the red channel of the atlas
+    // instead of thresholding the distance stored in its alpha channel
+    text_sdf: u32,
+    // The alpha is 0 when the outline is disabled
+    outline_color: vec4<f32>,
 }
 @group(0) @binding(0)
 var<uniform> config: OverlayConfig;
@@ -14,8 +42,16 @@ var<uniform> config: OverlayConfig;
 struct OverlayData {
     fps: f32,
     frame_count: u32,
-    resolution: vec2<u32>,
-    scale: f32,
+    graph_min: f32,
+    graph_max: f32,
+    pacing_target_interval: f32,
+    pacing_average_delta: f32,
+    pacing_deviating_frames: u32,
+    // Only used to format the text, see OverlayView
+    hovered_index: i32,
+    hovered_frametime: f32,
+    hovered_frame: u32,
+    hovered_timestamp: f32,
 }
 @group(0) @binding(1)
 var<uniform> data: OverlayData;
@@ -26,150 +62,150 @@ struct Frametimes {
 @group(0) @binding(2)
 var<storage> frametimes: Frametimes;
 
+// Additional series drawn as lines over the graph
+struct GraphSeriesValues {
+    values: array<array<f32, 64>, 4>,
+}
+@group(0) @binding(5)
+var<storage> series_values: GraphSeriesValues;
+
+struct Histogram {
+    // 0 when the histogram is disabled
+    bin_count: u32,
+    max_count: f32,
+    counts: array<f32, 32>,
+    centers: array<f32, 32>,
+}
+@group(0) @binding(6)
+var<storage> histogram: Histogram;
+
+// The advance of each glyph of the font, relative to the size of a cell
+struct GlyphAdvances {
+    values: array<f32, 256>,
+}
+@group(0) @binding(7)
+var<storage> glyph_advances: GlyphAdvances;
+
+// A line of text as indices of glyphs of the font atlas
+struct TextLine {
+    len: u32,
+    // Colors the text like a frame of this delta time when positive
+    color_dt: f32,
+    glyphs: array<u32, 32>,
+}
+
+// The text formatted on the cpu
+struct OverlayText {
+    // The text of each row, empty for the rows of the graphs, followed by the hover tooltip
+    rows: array<TextLine, 18>,
+    labels: array<TextLine, 4>,
+}
+
+// The values that depend on the target of the view
+struct OverlayView {
+    // The scale factor of the window, or 1 for an image, multiplied by the ui scale
+    layout_scale: f32,
+    // -1 when no frame is hovered or when the view isn't drawn on the primary window
+    hovered_index: i32,
+}
+@group(1) @binding(0)
+var<uniform> overlay_view: OverlayView;
+@group(1) @binding(1)
+var<storage> text: OverlayText;
+
 @group(0) @binding(3)
 var font_texture: texture_2d<f32>;
 @group(0) @binding(4)
 var font_sampler: sampler;
 
-// numbers
-let ch_0 = 48;
-let ch_1 = 49;
-let ch_2 = 50;
-let ch_3 = 51;
-let ch_4 = 52;
-let ch_5 = 53;
-let ch_6 = 54;
-let ch_7 = 55;
-let ch_8 = 56;
-let ch_9 = 57;
-
-// uppercase letters
-let ch_A = 65;
-let ch_B = 66;
-let ch_C = 67;
-let ch_D = 68;
-let ch_E = 69;
-let ch_F = 70;
-let ch_G = 71;
-let ch_H = 72;
-let ch_I = 73;
-let ch_J = 74;
-let ch_K = 75;
-let ch_L = 76;
-let ch_M = 77;
-let ch_N = 78;
-let ch_O = 79;
-let ch_P = 80;
-let ch_Q = 81;
-let ch_R = 82;
-let ch_S = 83;
-let ch_T = 84;
-let ch_U = 85;
-let ch_V = 86;
-let ch_W = 87;
-let ch_X = 88;
-let ch_Y = 89;
-let ch_Z = 90;
-let ch_a = 97;
-let ch_b = 98;
-let ch_c = 99;
-
-// lowercase letters
-let ch_d = 100;
-let ch_e = 101;
-let ch_f = 102;
-let ch_g = 103;
-let ch_h = 104;
-let ch_i = 105;
-let ch_j = 106;
-let ch_k = 107;
-let ch_l = 108;
-let ch_m = 109;
-let ch_n = 110;
-let ch_o = 111;
-let ch_p = 112;
-let ch_q = 113;
-let ch_r = 114;
-let ch_s = 115;
-let ch_t = 116;
-let ch_u = 117;
-let ch_v = 118;
-let ch_w = 119;
-let ch_x = 120;
-let ch_y = 121;
-let ch_z = 122;
-
-// symbols
-let ch_space = 32;
-let ch_dot = 46;
-let ch_colon = 58;
-let ch_lparen = 40;
-let ch_rparen = 41;
-let ch_percent = 37;
+// row kinds
+let ROW_TEXT: u32 = 0u;
+let ROW_GRAPH: u32 = 1u;
+let ROW_PACING_GRAPH: u32 = 2u;
+let ROW_HISTOGRAM: u32 = 3u;
+// The single row of the compact mode
+let ROW_COMPACT: u32 = 4u;
+
+// Size of the frametimes buffer, it needs to match FRAMETIME_BUFFER_LEN
+// The buffer is filled from the end, the most recent frame is the last one
+let FRAMETIME_BUFFER_LEN: i32 = 64;
+
+// Width of the overlay in compact mode
+let COMPACT_WIDTH: f32 = 220.0;
+// Amount of recent frames shown by the sparkline of the compact mode
+let SPARKLINE_FRAMES: i32 = 32;
+let SPARKLINE_WIDTH: f32 = 48.0;
+
+// graph scale modes
+let SCALE_LOG2: u32 = 0u;
+let SCALE_LINEAR: u32 = 1u;
+let SCALE_AUTO: u32 = 2u;
+
+// graph styles
+let STYLE_BARS: u32 = 0u;
+let STYLE_LINE: u32 = 1u;
+let STYLE_AREA: u32 = 2u;
+
+// Width of the lines of the graph in pixels
+let LINE_WIDTH: f32 = 1.5;
+// Opacity of the filled part of the area graph
+let AREA_ALPHA: f32 = 0.5;
+
+// Distance between two stripes of the hatching in pixels
+let HATCH_SPACING: f32 = 4.0;
+
+// Offset of the text shadow in pixels
+let SHADOW_OFFSET: vec2<f32> = vec2<f32>(1.0, 1.0);
+
+// Width of the text outline in pixels
+let OUTLINE_WIDTH: f32 = 1.0;
 
 let FONT_SIZE: f32 = 1.3;
+// Size of the budget line labels relative to the size of a row
+let LABEL_SIZE: f32 = 0.6;
 var<private> TEXT_CURRENT_POS: vec2<f32> = vec2<f32>(0., 0.);
+// x is the coverage of the glyphs and y the coverage of their outline
 var<private> TEXT_OUTPUT: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 0.0);
-var<private> ROW_COUNT: f32 = 0.0;
+// The size of a cell of the atlas in physical pixels
+var<private> TEXT_CELL_PIXELS: f32 = 1.0;
 
 // loosely based on <https://www.shadertoy.com/view/stVBRR>
 fn sdf_texture_char(pos: vec2<f32>, char_id: i32) -> vec4<f32> {
     let char_uv = pos / 16. + fract(vec2<f32>(f32(char_id), f32(char_id / 16)) / 16.);
     let char_sample = textureSample(font_texture, font_sampler, char_uv);
     if (pos.x < 0.0 || pos.x > 1. || pos.y < 0.0 || pos.y > 1.) {
-        return vec4<f32>(0.0);
+        // As far as possible from the glyph for the distance field
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
     }
     return char_sample;
 }
 
-// Prints the given character at the current cursor position
-fn print(c: i32) {
-    let out = sdf_texture_char(TEXT_CURRENT_POS, c);
-    TEXT_CURRENT_POS.x -= .5;
-    TEXT_OUTPUT += out;
-}
-
-// Moves the cursor to the next line
-fn newline(uv: vec2<f32>) {
-    TEXT_CURRENT_POS.x = (uv.x * 64. / FONT_SIZE);
-    TEXT_CURRENT_POS.y -= 1.;
-    ROW_COUNT += 1.0;
-}
-
-// Gets the charcode for the given number
-// Only works with digits, otherwise returns 0
-fn get_digit(in_value: f32) -> i32 {
-    var value = floor(in_value);
-    if (value >= 0.0 && value <= 9.0) {
-        return 48 + i32(value);
+// Gets the coverage of a glyph and of its outline from a sample of the atlas
+fn glyph_coverage(char_sample: vec4<f32>) -> vec4<f32> {
+    if (config.text_sdf == 0u) {
+        return vec4<f32>(char_sample.x, 0.0, 0.0, 0.0);
+    }
+    // The distance to the edge of the glyph in cells, negative inside of it
+    let dist = (char_sample.a - 0.5) * TEXT_CELL_PIXELS;
+    let fill = clamp(0.5 - dist, 0.0, 1.0);
+    var outline = 0.0;
+    if (config.outline_color.a > 0.0) {
+        outline = clamp(0.5 - dist + OUTLINE_WIDTH * overlay_view.layout_scale, 0.0, 1.0);
     }
-    return 0;
+    return vec4<f32>(fill, outline, 0.0, 0.0);
 }
 
-// Prints 4 numbers before the decimal point and 2 after
-fn print_number(number: f32) {
-    for (var i = 4; i >= -2; i -= 1) {
-        // get the digit at the current index
-        let digit = (number / pow(10., f32(i))) % 10.;
-        if (i == -1) {
-            // add decimal point
-            print(ch_dot);
-        }
-        if (abs(number) > pow(10., f32(i)) || i == 0) {
-            print(get_digit(digit));
-        }
-    }
+// Prints the given character at the current cursor position
+fn print(c: i32) {
+    let out = sdf_texture_char(TEXT_CURRENT_POS, c);
+    TEXT_CURRENT_POS.x -= glyph_advances.values[c];
+    TEXT_OUTPUT += glyph_coverage(out);
 }
 
-// prints u32 values with up to 8 digits
-fn print_u32(in_number: u32) {
-    var number = f32(in_number);
-    for (var i = 8; i >= 0; i -= 1) {
-        // get the digit at the current index
-        let digit = (number / pow(10., f32(i))) % 10.;
-        if (abs(number) > pow(10., f32(i))) {
-            print(get_digit(digit));
-        }
+// Prints a row of the text buffer at the current cursor position
+fn print_row(row: u32) {
+    for (var i = 0u; i < min(text.rows[row].len, 32u); i = i + 1u) {
+        print(i32(text.rows[row].glyphs[i]));
     }
 }
 
@@ -181,6 +217,31 @@ fn sdf_square(pos: vec2<f32>, half_size: vec2<f32>, offset: vec2<f32>) -> f32 {
     return outside_dist + inside_dist;
 }
 
+// Distance to a box centered on the origin with rounded corners
+fn sdf_rounded_box(pos: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let q = abs(pos) - half_size + radius;
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+// Distance between the position and the segment going from a to b
+fn sdf_segment(pos: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let pa = pos - a;
+    let ba = b - a;
+    // Avoid dividing by 0 for frames without a delta time
+    let h = clamp(dot(pa, ba) / max(dot(ba, ba), 0.0001), 0.0, 1.0);
+    return length(pa - ba * h);
+}
+
+// Blends the src color over the dst color
+fn blend_over(dst: vec4<f32>, src: vec4<f32>) -> vec4<f32> {
+    let alpha = src.a + dst.a * (1.0 - src.a);
+    if (alpha <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let rgb = (src.rgb * src.a + dst.rgb * dst.a * (1.0 - src.a)) / alpha;
+    return vec4<f32>(rgb, alpha);
+}
+
 // Gets a color based on the delta time
 // The colors are configured using the OverlayConfig
 fn color_from_dt(dt: f32) -> vec4<f32> {
@@ -197,34 +258,190 @@ fn color_from_dt(dt: f32) -> vec4<f32> {
     return config.colors[colors_count - 1];
 }
 
-fn draw_frametime_graph(uv: vec2<f32>, width: f32, height: f32, offset: f32) -> vec4<f32> {
-    // Frametime graph
-    let dt_min = config.dt_min;
-    let dt_max = config.dt_max;
-    let dt_min_log2 = config.dt_min_log2;
-    let dt_max_log2 = config.dt_max_log2;
-    let max_width = config.max_width;
+// Darkens every other diagonal stripe of the frames slower than the hatch threshold
+fn hatch(color: vec4<f32>, pixel: vec2<f32>, dt: f32) -> vec4<f32> {
+    if (config.hatch_threshold <= 0.0 || dt <= config.hatch_threshold) {
+        return color;
+    }
+    if (fract((pixel.x + pixel.y) / HATCH_SPACING) < 0.5) {
+        return vec4<f32>(color.rgb * 0.4, color.a);
+    }
+    return color;
+}
 
-    // The general alogrithm is highly inspired by
-    // <https://asawicki.info/news_1758_an_idea_for_visualization_of_frame_times>
-    // <https://github.com/sawickiap/RegEngine/blob/613c31fd60558a75c5b8902529acfa425fc97b2a/Source/Game.cpp#L331>
+// Gets the height of a bar in the graph for the given delta time, from 0 to 1
+// The scale is configured using the OverlayConfig
+fn height_from_dt(dt: f32) -> f32 {
+    var frame_height_factor: f32;
+    if (config.scale_mode == SCALE_LOG2) {
+        frame_height_factor = (log2(dt) - config.dt_min_log2) / max(config.dt_max_log2 - config.dt_min_log2, 0.0001);
+    } else {
+        // Both the linear and auto modes use the range computed on the cpu
+        // An empty range fills the whole height with the frames above it
+        frame_height_factor = (dt - data.graph_min) / max(data.graph_max - data.graph_min, 0.0001);
+    }
+    let frame_height_factor_norm = min(max(0.0, frame_height_factor), 1.0);
+    return mix(0.0, 1.0, frame_height_factor_norm);
+}
 
-    let graph_area = vec2<f32>(width, height);
-    let pos_in_area = (uv * vec2<f32>(1.0, -1.0) + graph_area + vec2<f32>(0.0, offset)) / graph_area;
+// Prints the label of a budget line with the top left corner of the text at the given position
+// Returns the text coverage without affecting the rest of the text output
+fn draw_label(uv: vec2<f32>, position: vec2<f32>, size: f32, index: i32) -> f32 {
+    let cursor = TEXT_CURRENT_POS;
+    let output = TEXT_OUTPUT;
+    let cell_pixels = TEXT_CELL_PIXELS;
+
+    TEXT_CURRENT_POS = (uv - position) / size;
+    TEXT_OUTPUT = vec4<f32>(0.0);
+    TEXT_CELL_PIXELS = size * overlay_view.layout_scale;
+    for (var i = 0u; i < min(text.labels[index].len, 32u); i = i + 1u) {
+        print(i32(text.labels[index].glyphs[i]));
+    }
+    let label = TEXT_OUTPUT.x;
+
+    TEXT_CURRENT_POS = cursor;
+    TEXT_OUTPUT = output;
+    TEXT_CELL_PIXELS = cell_pixels;
+    return label;
+}
+
+// Draws the budget lines and their labels over the graph
+fn draw_budget_lines(uv: vec2<f32>, pos_in_area: vec2<f32>, height: f32, offset: f32) -> vec4<f32> {
+    if (pos_in_area.x < 1.0 || pos_in_area.x > 2.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let label_size = FONT_SIZE * 16. * LABEL_SIZE;
+    for (var i = 0; i < i32(config.budget_line_count); i = i + 1) {
+        let line_height = height_from_dt(config.budget_line_dts[i]);
+        let line_y = offset + (1.0 - line_height) * height;
+        if (abs(uv.y - line_y) < 0.5) {
+            return config.budget_line_colors[i];
+        }
+
+        if (config.budget_line_labels[i] != 0u) {
+            // Keep the label inside the graph even when the line is at the top
+            let label_y = max(line_y - label_size * 0.8, offset);
+            let label = draw_label(uv, vec2<f32>(2.0, label_y), label_size, i);
+            if (label > 0.0) {
+                return vec4<f32>(config.budget_line_colors[i].rgb, label);
+            }
+        }
+    }
+    return vec4<f32>(0.0);
+}
+
+// Index of the first of the config.len most recent frames drawn by the graph
+fn graph_start() -> i32 {
+    return FRAMETIME_BUFFER_LEN - config.len;
+}
+
+// Index of the oldest frame visible in the graph
+fn first_frame() -> i32 {
+    // With fixed width bars, only the most recent frames are shown
+    if (config.bar_count > 0u) {
+        return FRAMETIME_BUFFER_LEN - i32(config.bar_count);
+    }
+    return graph_start();
+}
+
+// Width of the frame relative to the width of the graph
+fn frame_width(dt: f32) -> f32 {
+    if (config.bar_count > 0u) {
+        return 1.0 / f32(config.bar_count);
+    }
+    return (dt / config.dt_min) / config.max_width;
+}
+
+// Gets the delta time of a frame for the given series
+// A negative series is the frametimes
+fn series_value(series: i32, i: i32) -> f32 {
+    if (series < 0) {
+        return frametimes.values[i];
+    }
+    return series_values.values[series][i];
+}
+
+// Finds the closest segment of the line going through the top center of each frame
+// Returns the distance in pixels and the delta time of the closest current_point of the segment
+fn line_distance(pixel: vec2<f32>, graph_area: vec2<f32>, series: i32) -> vec2<f32> {
+    var closest = vec2<f32>(1000000.0, 0.0);
     var graph_width = 0.0;
-    for (var i = 0; i <= config.len; i = i + 1) {
+    var prev_point = vec2<f32>(0.0);
+    var prev_dt = 0.0;
+    for (var i = first_frame(); i < FRAMETIME_BUFFER_LEN; i = i + 1) {
+        let frame_width = frame_width(frametimes.values[i]);
+        let dt = series_value(series, i);
+        let current_point = vec2<f32>(graph_width + frame_width / 2., height_from_dt(dt)) * graph_area;
+        if (i > first_frame()) {
+            let dist = sdf_segment(pixel, prev_point, current_point);
+            if (dist < closest.x) {
+                let t = clamp((pixel.x - prev_point.x) / max(current_point.x - prev_point.x, 0.0001), 0.0, 1.0);
+                closest = vec2<f32>(dist, mix(prev_dt, dt, t));
+            }
+        }
+        prev_point = current_point;
+        prev_dt = dt;
+        graph_width = graph_width + frame_width;
+    }
+    return closest;
+}
+
+// Gets the coverage of a line at the given distance in pixels
+fn line_coverage(dist: f32) -> f32 {
+    if (config.antialiasing != 0u) {
+        return clamp(LINE_WIDTH / 2. + 0.5 - dist, 0.0, 1.0);
+    }
+    return select(0.0, 1.0, dist < LINE_WIDTH / 2.);
+}
+
+// Fills the area under the line of the frametimes
+fn draw_area(pixel: vec2<f32>, graph_area: vec2<f32>) -> vec4<f32> {
+    var graph_width = 0.0;
+    var prev_point = vec2<f32>(0.0);
+    var prev_dt = 0.0;
+    for (var i = first_frame(); i < FRAMETIME_BUFFER_LEN; i = i + 1) {
+        let frame_width = frame_width(frametimes.values[i]);
         let dt = frametimes.values[i];
-        let frame_width = (dt / dt_min);
-        let frame_width = frame_width / max_width;
+        let current_point = vec2<f32>(graph_width + frame_width / 2., height_from_dt(dt)) * graph_area;
+        if (i > first_frame() && pixel.x >= prev_point.x && pixel.x < current_point.x) {
+            let t = (pixel.x - prev_point.x) / (current_point.x - prev_point.x);
+            let area_height = mix(prev_point.y, current_point.y, t);
+            var coverage = select(0.0, 1.0, pixel.y <= area_height);
+            if (config.antialiasing != 0u) {
+                coverage = clamp(area_height + 0.5 - pixel.y, 0.0, 1.0);
+            }
+            let area_dt = mix(prev_dt, dt, t);
+            let color = hatch(color_from_dt(area_dt), pixel, area_dt);
+            return vec4<f32>(color.rgb, color.a * AREA_ALPHA * coverage);
+        }
+        prev_point = current_point;
+        prev_dt = dt;
+        graph_width = graph_width + frame_width;
+    }
+    return vec4<f32>(0.0);
+}
 
-        let frame_height_factor = (log2(dt) - dt_min_log2) / (dt_max_log2 - dt_min_log2);
-        let frame_height_factor_norm = min(max(0.0, frame_height_factor), 1.0);
-        let frame_height = mix(0.0, 1.0, frame_height_factor_norm);
+// Gets the horizontal range covered by the given frame, relative to the width of the graph
+fn frame_range(index: i32) -> vec2<f32> {
+    var graph_width = 0.0;
+    for (var i = first_frame(); i < index; i = i + 1) {
+        graph_width = graph_width + frame_width(frametimes.values[i]);
+    }
+    return vec2<f32>(graph_width, graph_width + frame_width(frametimes.values[index]));
+}
+
+fn draw_bars(pos_in_area: vec2<f32>, pixel: vec2<f32>) -> vec4<f32> {
+    var graph_width = 0.0;
+    for (var i = first_frame(); i < FRAMETIME_BUFFER_LEN; i = i + 1) {
+        let dt = frametimes.values[i];
+        let frame_width = frame_width(dt);
+        let frame_height = height_from_dt(dt);
 
         let size = vec2<f32>(frame_width, frame_height) / 2.;
         let offset = vec2<f32>(1. + graph_width + frame_width / 2., frame_height / 2.);
         if (sdf_square(pos_in_area, size, offset) < 0.0) {
-            return color_from_dt(dt);
+            return hatch(color_from_dt(dt), pixel, dt);
         }
 
         graph_width = graph_width + frame_width;
@@ -232,6 +449,100 @@ fn draw_frametime_graph(uv: vec2<f32>, width: f32, height: f32, offset: f32) ->
     return vec4<f32>(0.0);
 }
 
+fn draw_frametime_graph(uv: vec2<f32>, width: f32, height: f32, offset: f32) -> vec4<f32> {
+    // The general alogrithm is highly inspired by
+    // <https://asawicki.info/news_1758_an_idea_for_visualization_of_frame_times>
+    // <https://github.com/sawickiap/RegEngine/blob/613c31fd60558a75c5b8902529acfa425fc97b2a/Source/Game.cpp#L331>
+
+    let graph_area = vec2<f32>(width, height);
+    let pos_in_area = (uv * vec2<f32>(1.0, -1.0) + graph_area + vec2<f32>(0.0, offset)) / graph_area;
+    if (pos_in_area.y < 0.0 || pos_in_area.y > 1.0) {
+        return vec4<f32>(0.0);
+    }
+    // position in pixels from the bottom left of the graph
+    let pixel = (pos_in_area - vec2<f32>(1.0, 0.0)) * graph_area;
+
+    var color = vec4<f32>(0.0);
+    if (config.graph_style == STYLE_BARS) {
+        color = draw_bars(pos_in_area, pixel);
+    } else {
+        if (config.graph_style == STYLE_AREA) {
+            color = draw_area(pixel, graph_area);
+        }
+        let closest = line_distance(pixel, graph_area, -1);
+        let line_color = color_from_dt(closest.y);
+        color = blend_over(color, vec4<f32>(line_color.rgb, line_color.a * line_coverage(closest.x)));
+    }
+
+    // highlight the hovered frame
+    if (overlay_view.hovered_index >= first_frame()) {
+        let hovered_range = frame_range(overlay_view.hovered_index);
+        let x = pos_in_area.x - 1.0;
+        if (x >= hovered_range.x && x < hovered_range.y) {
+            color = blend_over(color, vec4<f32>(1.0, 1.0, 1.0, 0.3));
+        }
+    }
+
+    for (var series = 0; series < i32(config.series_count); series = series + 1) {
+        let closest = line_distance(pixel, graph_area, series);
+        let line_color = config.series_colors[series];
+        color = blend_over(color, vec4<f32>(line_color.rgb, line_color.a * line_coverage(closest.x)));
+    }
+
+    let budget_line_color = draw_budget_lines(uv, pos_in_area, height, offset);
+    return blend_over(color, budget_line_color);
+}
+
+// Draws the difference between the frametime of each frame and the previous one
+// The frames use the same layout as the frametime graph
+// A difference as big as the target interval fills the whole height
+fn draw_pacing_graph(uv: vec2<f32>, width: f32, height: f32, offset: f32) -> vec4<f32> {
+    // position from the bottom left of the graph, from 0 to 1
+    let pos = vec2<f32>(uv.x / width, 1.0 - (uv.y - offset) / height);
+    if (pos.x < 0.0 || pos.x > 1.0 || pos.y < 0.0 || pos.y > 1.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let target_interval = max(data.pacing_target_interval, 0.0001);
+    var graph_width = 0.0;
+    for (var i = first_frame(); i < FRAMETIME_BUFFER_LEN; i = i + 1) {
+        let dt = frametimes.values[i];
+        let frame_width = frame_width(dt);
+        if (i > 0 && pos.x >= graph_width && pos.x < graph_width + frame_width) {
+            let delta = abs(dt - frametimes.values[i - 1]);
+            if (pos.y <= min(delta / target_interval, 1.0)) {
+                let t = clamp(delta / (target_interval * config.pacing_tolerance), 0.0, 1.0);
+                return mix(config.colors[0], config.colors[3], t);
+            }
+            return vec4<f32>(0.0);
+        }
+        graph_width = graph_width + frame_width;
+    }
+    return vec4<f32>(0.0);
+}
+
+fn draw_histogram(uv: vec2<f32>, width: f32, height: f32, offset: f32) -> vec4<f32> {
+    // position from the bottom left of the histogram, from 0 to 1
+    let pos = vec2<f32>(uv.x / width, 1.0 - (uv.y - offset) / height);
+    if (pos.x < 0.0 || pos.x >= 1.0 || pos.y < 0.0 || pos.y > 1.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let bin_count = f32(histogram.bin_count);
+    let bin = u32(pos.x * bin_count);
+    // leave a gap of a pixel between the bins
+    let bin_width = width / bin_count;
+    if (fract(pos.x * bin_count) * bin_width < 1.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let bin_height = histogram.counts[bin] / max(histogram.max_count, 1.0);
+    if (pos.y <= bin_height) {
+        return color_from_dt(histogram.centers[bin]);
+    }
+    return vec4<f32>(0.0);
+}
+
 struct VertexOutput {
     @builtin(position) uv: vec4<f32>,
 }
@@ -246,67 +557,111 @@ fn vertex(
     return out;
 }
 
+// Draws the most recent frames as small bars in the given area
+fn draw_sparkline(uv: vec2<f32>, position: vec2<f32>, size: vec2<f32>) -> vec4<f32> {
+    let pos = (uv - position) / size;
+    if (pos.x < 0.0 || pos.x >= 1.0 || pos.y < 0.0 || pos.y > 1.0) {
+        return vec4<f32>(0.0);
+    }
+
+    let count = min(SPARKLINE_FRAMES, config.len);
+    let dt = frametimes.values[FRAMETIME_BUFFER_LEN - count + i32(pos.x * f32(count))];
+    if (1.0 - pos.y <= height_from_dt(dt)) {
+        return color_from_dt(dt);
+    }
+    return vec4<f32>(0.0);
+}
+
+// The amount of rows of the overlay, including the hover tooltip
+fn total_row_count() -> u32 {
+    if (overlay_view.hovered_index >= 0) {
+        return config.row_count + 2u;
+    }
+    return config.row_count;
+}
+
+// Gets the color of the text of the row under the given position
+fn text_color(uv: vec2<f32>) -> vec4<f32> {
+    let row = floor(uv.y / (FONT_SIZE * 16.));
+    if (row >= 0.0 && row < f32(total_row_count())) {
+        let color_dt = text.rows[u32(row)].color_dt;
+        if (color_dt > 0.0) {
+            return vec4<f32>(color_from_dt(color_dt).rgb, config.text_color.a);
+        }
+    }
+    return config.text_color;
+}
+
+// Prints the row of text under the given position
+// Returns the coverage of the text and of its outline
+fn draw_text(uv: vec2<f32>) -> vec2<f32> {
+    // A cell of the atlas covers a row
+    let pos = uv / (FONT_SIZE * 16.);
+    let row = floor(pos.y);
+    if (row < 0.0 || row >= f32(total_row_count())) {
+        return vec2<f32>(0.0);
+    }
+
+    TEXT_CURRENT_POS = vec2<f32>(pos.x, pos.y - row);
+    TEXT_OUTPUT = vec4<f32>(0.0);
+    TEXT_CELL_PIXELS = FONT_SIZE * 16. * overlay_view.layout_scale;
+    print_row(u32(row));
+    return min(TEXT_OUTPUT.xy, vec2<f32>(1.0));
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let background = vec4<f32>(0.0, 0.0, 0.0, 0.4);
-    let area_width = 175.0;
+    var area_width = 175.0;
+    if (config.rows[0][0] == ROW_COMPACT) {
+        area_width = COMPACT_WIDTH;
+    }
     let row_height = FONT_SIZE * 16.;
     let graph_height = row_height;
-    let total_area = vec2<f32>(area_width, row_height * 5.);
-    let font_uv = in.uv.xy / f32(textureDimensions(font_texture).y);
-    if (in.uv.x > total_area.x || in.uv.y > total_area.y) {
+    let row_count = f32(total_row_count());
+    // position in pixels of the layout, from the top left corner of the overlay
+    let layout_pos = in.uv.xy / overlay_view.layout_scale;
+    let total_area = vec2<f32>(area_width, row_height * row_count) + 2.0 * config.padding;
+    if (layout_pos.x > total_area.x || layout_pos.y > total_area.y) {
+        discard;
+    }
+    let corner_dist = sdf_rounded_box(layout_pos - total_area / 2.0, total_area / 2.0, config.corner_radius);
+    // antialiased over a physical pixel
+    let corner_coverage = clamp(0.5 - corner_dist * overlay_view.layout_scale, 0.0, 1.0);
+    if (corner_coverage <= 0.0) {
         discard;
     }
 
-    TEXT_CURRENT_POS = font_uv * 64. / FONT_SIZE;
-
-    // fps
-    print_number(data.fps);
-    print(ch_space);
-    print(ch_f);
-    print(ch_p);
-    print(ch_s);
-    newline(font_uv);
-
-    // frametime in ms
-    let dt = frametimes.values[config.len - 1] * 1000.;
-    print_number(dt);
-    print(ch_m);
-    print(ch_s);
-    newline(font_uv);
-
-    // frame count since start
-    print(ch_F);
-    print(ch_r);
-    print(ch_a);
-    print(ch_m);
-    print(ch_e);
-    print(ch_colon);
-    print(ch_space);
-    print_u32(data.frame_count);
-    newline(font_uv);
-
-    // resolution and scale
-    print_u32(data.resolution.x);
-    print(ch_x);
-    print_u32(data.resolution.y);
-    print(ch_space);
-    print(ch_lparen);
-    print_u32(u32(data.scale * 100.));
-    print(ch_percent);
-    print(ch_rparen);
-    newline(font_uv);
-
-    //frametime graph
-    var graph_color = draw_frametime_graph(in.uv.xy, area_width, graph_height, ROW_COUNT * row_height);
-    if (any(graph_color != vec4<f32>(0.0))) {
-        return graph_color;
-    }
-    newline(font_uv);
-
-    if (TEXT_OUTPUT.x > 0.0) {
-        return TEXT_OUTPUT.xxxx;
-    } else {
-        return background;
+    // position relative to the top left corner of the content
+    let uv = layout_pos - config.padding;
+    var color = config.background;
+
+    // the graphs of the row under the pixel
+    let row = floor(uv.y / row_height);
+    if (row >= 0.0 && row < f32(config.row_count)) {
+        let index = u32(row);
+        let kind = config.rows[index / 4u][index % 4u];
+        let offset = row * row_height;
+        if (kind == ROW_GRAPH) {
+            color = blend_over(color, draw_frametime_graph(uv, area_width, graph_height, offset));
+        } else if (kind == ROW_PACING_GRAPH) {
+            color = blend_over(color, draw_pacing_graph(uv, area_width, graph_height, offset));
+        } else if (kind == ROW_HISTOGRAM) {
+            color = blend_over(color, draw_histogram(uv, area_width, graph_height, offset));
+        } else if (kind == ROW_COMPACT) {
+            let sparkline_position = vec2<f32>(area_width - SPARKLINE_WIDTH - 2.0, offset + row_height * 0.15);
+            let sparkline_size = vec2<f32>(SPARKLINE_WIDTH, row_height * 0.7);
+            color = blend_over(color, draw_sparkline(uv, sparkline_position, sparkline_size));
+        }
     }
+
+    if (config.shadow_color.a > 0.0) {
+        let shadow = draw_text(uv - SHADOW_OFFSET);
+        color = blend_over(color, vec4<f32>(config.shadow_color.rgb, config.shadow_color.a * max(shadow.x, shadow.y)));
+    }
+    let text = draw_text(uv);
+    color = blend_over(color, vec4<f32>(config.outline_color.rgb, config.outline_color.a * text.y));
+    let fill_color = text_color(uv);
+    color = blend_over(color, vec4<f32>(fill_color.rgb, fill_color.a * text.x));
+
+    return vec4<f32>(color.rgb, color.a * corner_coverage);
 }
@@ -1,5 +1,7 @@
 use bevy::{asset::AssetServerSettings, prelude::*};
-use bevy_prototype_frametime_display_plugin::{CameraOverlay, OverlayConfig, OverlayPlugin};
+use bevy_prototype_frametime_display_plugin::{
+    BudgetLine, CameraOverlay, OverlayConfig, OverlayMode, OverlayPlugin,
+};
 
 fn main() {
     App::new()
@@ -9,13 +11,26 @@ fn main() {
         })
         .add_plugins(DefaultPlugins)
         // If you need to configure it
-        .insert_resource(OverlayConfig { ..default() })
+        .insert_resource(OverlayConfig {
+            budget_lines: vec![BudgetLine::from_fps(60., Color::WHITE)],
+            ..default()
+        })
         // Insert the plugin on the app
-        .add_plugin(OverlayPlugin)
+        // The overlay is drawn at the end of the render graph of the camera tagged with CameraOverlay
+        .add_plugin(OverlayPlugin {
+            mode: OverlayMode::RenderGraph,
+            ..default()
+        })
+        .add_startup_system(load_overlay_settings)
         .add_startup_system(setup_3d_scene)
         .run();
 }
 
+// The settings override the config and are reloaded every time the file is modified
+fn load_overlay_settings(asset_server: Res<AssetServer>, mut config: ResMut<OverlayConfig>) {
+    config.settings = Some(asset_server.load("example.overlay.ron"));
+}
+
 // This is simply the scene from the 3d_scene example of bevy
 fn setup_3d_scene(
     mut commands: Commands,
@@ -0,0 +1,43 @@
+use bevy::{prelude::*, render::camera::RenderTarget};
+use bevy_prototype_frametime_display_plugin::{overlay_image, OverlayPlugin};
+
+#[derive(Resource)]
+struct OverlayImage(Handle<Image>);
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+
+    // The overlay is 175 pixels wide and each row is about 21 pixels high
+    let image = overlay_image(UVec2::new(175, 105));
+    let image = app.world.resource_mut::<Assets<Image>>().add(image);
+
+    app.insert_resource(OverlayImage(image.clone()))
+        // Render the overlay to the image instead of the window
+        .add_plugin(OverlayPlugin {
+            target: RenderTarget::Image(image),
+            ..default()
+        })
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, overlay_image: Res<OverlayImage>) {
+    commands.spawn(Camera2dBundle::default());
+
+    // Show the overlay in the bottom right corner of the window, twice as big
+    commands.spawn(ImageBundle {
+        style: Style {
+            size: Size::new(Val::Px(350.0), Val::Px(210.0)),
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        },
+        image: UiImage(overlay_image.0.clone()),
+        ..default()
+    });
+}
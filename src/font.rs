@@ -0,0 +1,253 @@
+use ab_glyph::{point, Font as _, PxScale, ScaleFont};
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, ShaderType, TextureDimension, TextureFormat},
+};
+
+use crate::OverlayConfig;
+
+/// The amount of cells in each row and column of a font atlas
+const ATLAS_GRID: u32 = 16;
+/// The advance of every glyph of the default font, relative to the size of a cell
+const DEFAULT_ADVANCE: f32 = 0.5;
+
+/// How the coverage of the glyphs is read from the font atlas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub enum TextRendering {
+    /// Reads the coverage from the red channel of the atlas.
+    /// The text gets blurry when it's scaled up.
+    Bitmap,
+    /// Thresholds the distance to the edge of the glyphs stored in the alpha channel of the atlas,
+    /// which keeps the text sharp at any scale and supports [`crate::OverlayTheme::text_outline`].
+    ///
+    /// The alpha is 0.5 on the edge of the glyphs and grows by 1 for every cell away from it.
+    #[default]
+    Sdf,
+}
+
+/// A TTF or OTF font rasterized into an atlas when it's loaded
+#[derive(Debug, Clone, Reflect, FromReflect)]
+pub struct OverlayFont {
+    pub font: Handle<Font>,
+    /// The size of a cell of the atlas in pixels.
+    /// The glyphs are rasterized at half that size, like the default font.
+    ///
+    /// Defaults to 64
+    pub cell_size: u32,
+}
+
+impl OverlayFont {
+    pub fn new(font: Handle<Font>) -> Self {
+        Self {
+            font,
+            cell_size: 64,
+        }
+    }
+}
+
+/// The atlas used to draw the text of the overlay
+#[derive(Resource, Clone)]
+pub struct FontImage(pub(crate) Handle<Image>);
+
+/// The horizontal advance of each glyph of the atlas, relative to the size of a cell
+#[derive(Debug, Clone, ShaderType, Resource)]
+pub struct GlyphAdvances {
+    pub values: [f32; 256],
+}
+
+impl Default for GlyphAdvances {
+    fn default() -> Self {
+        Self {
+            values: [DEFAULT_ADVANCE; 256],
+        }
+    }
+}
+
+/// Computes the distance in pixels from every pixel of the grid to the closest pixel matching the mask
+///
+/// Uses the 8-point sequential euclidean distance transform, which is precise enough for glyphs.
+fn distance_transform(size: usize, mask: impl Fn(usize) -> bool) -> Vec<f32> {
+    let far = IVec2::splat(size as i32 * 2);
+    let mut grid: Vec<IVec2> = (0..size * size)
+        .map(|i| if mask(i) { IVec2::ZERO } else { far })
+        .collect();
+
+    let compare = |grid: &mut Vec<IVec2>, x: i32, y: i32, offset: IVec2| {
+        let neighbor = IVec2::new(x, y) + offset;
+        if neighbor.x < 0
+            || neighbor.y < 0
+            || neighbor.x >= size as i32
+            || neighbor.y >= size as i32
+        {
+            return;
+        }
+        let candidate = grid[neighbor.y as usize * size + neighbor.x as usize] + offset;
+        let current = &mut grid[y as usize * size + x as usize];
+        if candidate.dot(candidate) < current.dot(*current) {
+            *current = candidate;
+        }
+    };
+
+    let size = size as i32;
+    for y in 0..size {
+        for x in 0..size {
+            compare(&mut grid, x, y, IVec2::new(-1, 0));
+            compare(&mut grid, x, y, IVec2::new(0, -1));
+            compare(&mut grid, x, y, IVec2::new(-1, -1));
+            compare(&mut grid, x, y, IVec2::new(1, -1));
+        }
+        for x in (0..size).rev() {
+            compare(&mut grid, x, y, IVec2::new(1, 0));
+        }
+    }
+    for y in (0..size).rev() {
+        for x in (0..size).rev() {
+            compare(&mut grid, x, y, IVec2::new(1, 0));
+            compare(&mut grid, x, y, IVec2::new(0, 1));
+            compare(&mut grid, x, y, IVec2::new(-1, 1));
+            compare(&mut grid, x, y, IVec2::new(1, 1));
+        }
+        for x in 0..size {
+            compare(&mut grid, x, y, IVec2::new(-1, 0));
+        }
+    }
+
+    grid.iter()
+        .map(|offset| offset.as_vec2().length())
+        .collect()
+}
+
+/// Converts the coverage of a cell to the distance to the edge of the glyph in pixels, negative inside of it
+fn signed_distance(coverage: &[f32], size: usize) -> Vec<f32> {
+    let inside = |i: usize| coverage[i] >= 0.5;
+    let to_inside = distance_transform(size, inside);
+    let to_outside = distance_transform(size, |i| !inside(i));
+    // The edge is between the centers of the last pixel inside and the first one outside
+    (0..size * size)
+        .map(|i| {
+            if inside(i) {
+                0.5 - to_outside[i]
+            } else {
+                to_inside[i] - 0.5
+            }
+        })
+        .collect()
+}
+
+/// Rasterizes the first 256 code points of the font in a grid of 16x16 cells, laid out like the default font
+///
+/// The coverage of the glyphs is stored in the color channels and their signed distance field in the
+/// alpha channel, see [`TextRendering::Sdf`].
+fn build_atlas(font: &Font, cell_size: u32) -> (Image, GlyphAdvances) {
+    let size = cell_size * ATLAS_GRID;
+    let mut data = vec![0; (size * size * 4) as usize];
+    let mut advances = GlyphAdvances::default();
+
+    // The line spans the middle half of the cell and the glyphs start a quarter of a cell from its left side
+    let font = font.font.as_scaled(PxScale::from(cell_size as f32 * 0.5));
+    let margin = cell_size as f32 * 0.25;
+    let baseline = margin + (cell_size as f32 * 0.5 - font.height()) / 2.0 + font.ascent();
+
+    let cell_len = cell_size as usize;
+    let mut coverage = vec![0.0; cell_len * cell_len];
+    for (code, advance) in advances.values.iter_mut().enumerate() {
+        let cell = UVec2::new(code as u32 % ATLAS_GRID, code as u32 / ATLAS_GRID) * cell_size;
+        coverage.fill(0.0);
+
+        let c = char::from(code as u8);
+        if !c.is_control() {
+            let glyph_id = font.glyph_id(c);
+            *advance = font.h_advance(glyph_id) / cell_size as f32;
+
+            let glyph = glyph_id.with_scale_and_position(font.scale(), point(margin, baseline));
+            if let Some(outline) = font.outline_glyph(glyph) {
+                let bounds = outline.px_bounds();
+                outline.draw(|x, y, value| {
+                    let x = bounds.min.x as i32 + x as i32;
+                    let y = bounds.min.y as i32 + y as i32;
+                    // Glyphs overflowing their cell are clipped
+                    if x >= 0 && y >= 0 && x < cell_size as i32 && y < cell_size as i32 {
+                        coverage[y as usize * cell_len + x as usize] = value.clamp(0.0, 1.0);
+                    }
+                });
+            }
+        }
+
+        let distances = signed_distance(&coverage, cell_len);
+        for (i, (value, dist)) in coverage.iter().zip(distances).enumerate() {
+            let x = cell.x as usize + i % cell_len;
+            let y = cell.y as usize + i / cell_len;
+            let value = (value * 255.0) as u8;
+            let dist = ((0.5 + dist / cell_size as f32).clamp(0.0, 1.0) * 255.0) as u8;
+            let index = (y * size as usize + x) * 4;
+            data[index..index + 4].copy_from_slice(&[value, value, value, dist]);
+        }
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+    );
+    (image, advances)
+}
+
+pub(crate) fn load_font(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // TODO embed font in plugin
+    commands.insert_resource(FontImage(asset_server.load("font.png")));
+}
+
+/// Picks the atlas matching the [`OverlayConfig`] and builds it when using an [`OverlayFont`]
+pub(crate) fn update_font(
+    config: Res<OverlayConfig>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<Assets<Font>>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    mut images: ResMut<Assets<Image>>,
+    mut font_image: ResMut<FontImage>,
+    mut advances: ResMut<GlyphAdvances>,
+    mut built_font: Local<Option<(Handle<Font>, u32)>>,
+) {
+    let overlay_font = match &config.font {
+        Some(overlay_font) => overlay_font,
+        None => {
+            if !config.is_changed() {
+                return;
+            }
+            let image = match &config.font_handle {
+                Some(image) => image.clone(),
+                None => asset_server.load("font.png"),
+            };
+            if font_image.0 != image {
+                font_image.0 = image;
+                *advances = GlyphAdvances::default();
+                *built_font = None;
+            }
+            return;
+        }
+    };
+
+    let font_modified = font_events.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+            *handle == overlay_font.font
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    let key = (overlay_font.font.clone(), overlay_font.cell_size.max(1));
+    if !font_modified && built_font.as_ref() == Some(&key) {
+        return;
+    }
+
+    // Keep the current atlas until the font is loaded
+    if let Some(font) = fonts.get(&key.0) {
+        let (image, glyph_advances) = build_atlas(font, key.1);
+        font_image.0 = images.add(image);
+        *advances = glyph_advances;
+        *built_font = Some(key);
+    }
+}
@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The glyph drawn for the characters missing from the font atlas
+const REPLACEMENT_GLYPH: u32 = '?' as u32;
+
+/// How the numbers of the overlay are formatted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+#[serde(default)]
+pub struct NumberFormat {
+    /// The amount of digits after the decimal point
+    ///
+    /// Defaults to 2
+    pub decimals: usize,
+    /// Inserted between every group of three digits of the integer part, like 1,000,000
+    ///
+    /// Defaults to None
+    pub thousands_separator: Option<char>,
+    pub units: NumberUnits,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimals: 2,
+            thousands_separator: None,
+            units: NumberUnits::default(),
+        }
+    }
+}
+
+/// The units appended to the numbers of the overlay, including the space before them if any
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+#[serde(default)]
+pub struct NumberUnits {
+    /// Defaults to " fps"
+    pub fps: String,
+    /// The unit of the frametimes, which are shown in milliseconds
+    ///
+    /// Defaults to "ms"
+    pub milliseconds: String,
+    /// The unit of the timestamp of the hovered frame
+    ///
+    /// Defaults to "s"
+    pub seconds: String,
+}
+
+impl Default for NumberUnits {
+    fn default() -> Self {
+        Self {
+            fps: " fps".to_string(),
+            milliseconds: "ms".to_string(),
+            seconds: "s".to_string(),
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Formats the value rounded to [`NumberFormat::decimals`] followed by the unit
+    pub fn format(&self, value: f64, unit: &str) -> String {
+        if !value.is_finite() {
+            let text = if value.is_nan() {
+                "NaN"
+            } else if value > 0.0 {
+                "inf"
+            } else {
+                "-inf"
+            };
+            return format!("{text}{unit}");
+        }
+
+        let text = format!("{:.*}", self.decimals, value);
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text.as_str(), None),
+        };
+        // Values rounded to 0 lose their sign, like -0.001 with 2 decimals
+        let is_zero = text.chars().all(|c| matches!(c, '-' | '0' | '.'));
+        let (sign, digits) = match integer.strip_prefix('-') {
+            Some(digits) if !is_zero => ("-", digits),
+            Some(digits) => ("", digits),
+            None => ("", integer),
+        };
+
+        let mut result = String::from(sign);
+        result.push_str(&self.group_digits(digits));
+        if let Some(fraction) = fraction {
+            result.push('.');
+            result.push_str(fraction);
+        }
+        result.push_str(unit);
+        result
+    }
+
+    /// Formats a frame rate followed by [`NumberUnits::fps`]
+    pub fn format_fps(&self, fps: f32) -> String {
+        self.format(fps as f64, &self.units.fps)
+    }
+
+    /// Formats a duration in seconds as milliseconds followed by [`NumberUnits::milliseconds`]
+    pub fn format_ms(&self, seconds: f32) -> String {
+        self.format(seconds as f64 * 1000.0, &self.units.milliseconds)
+    }
+
+    /// Formats a duration in seconds followed by [`NumberUnits::seconds`]
+    pub fn format_seconds(&self, seconds: f32) -> String {
+        self.format(seconds as f64, &self.units.seconds)
+    }
+
+    /// Formats an integer followed by the unit, ignoring [`NumberFormat::decimals`]
+    pub fn format_integer(&self, value: u64, unit: &str) -> String {
+        format!("{}{unit}", self.group_digits(&value.to_string()))
+    }
+
+    fn group_digits(&self, digits: &str) -> String {
+        let separator = match self.thousands_separator {
+            Some(separator) => separator,
+            None => return digits.to_string(),
+        };
+        // The first group is shorter when the amount of digits isn't a multiple of 3
+        let first_group = digits.len() % 3;
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && i % 3 == first_group {
+                result.push(separator);
+            }
+            result.push(digit);
+        }
+        result
+    }
+}
+
+/// Converts the text to the indices of its glyphs in the font atlas
+///
+/// The atlas covers the first 256 code points, other characters are replaced by a question mark.
+pub(crate) fn glyphs(text: &str) -> impl Iterator<Item = u32> + '_ {
+    text.chars().map(|c| {
+        let code = c as u32;
+        if code < 256 {
+            code
+        } else {
+            REPLACEMENT_GLYPH
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_powers_of_ten() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format(100.0, " fps"), "100.00 fps");
+        assert_eq!(format.format(1000.0, ""), "1000.00");
+        assert_eq!(format.format(10.0, "ms"), "10.00ms");
+        assert_eq!(format.format(1.0, ""), "1.00");
+    }
+
+    #[test]
+    fn zero() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format(0.0, ""), "0.00");
+        assert_eq!(format.format_integer(0, ""), "0");
+        assert_eq!(format.format(-0.001, ""), "0.00");
+    }
+
+    #[test]
+    fn precision() {
+        let format = NumberFormat {
+            decimals: 0,
+            ..NumberFormat::default()
+        };
+        assert_eq!(format.format(59.6, " fps"), "60 fps");
+
+        let format = NumberFormat {
+            decimals: 3,
+            ..NumberFormat::default()
+        };
+        assert_eq!(format.format(16.6666, "ms"), "16.667ms");
+    }
+
+    #[test]
+    fn rounding_carries_to_the_integer_part() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format(9.999, ""), "10.00");
+        assert_eq!(format.format(0.004, ""), "0.00");
+    }
+
+    #[test]
+    fn large_integers_are_not_truncated() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format_integer(1_234_567_890, ""), "1234567890");
+        assert_eq!(format.format(123_456_789.0, ""), "123456789.00");
+    }
+
+    #[test]
+    fn thousands_separator() {
+        let format = NumberFormat {
+            thousands_separator: Some(','),
+            ..NumberFormat::default()
+        };
+        assert_eq!(format.format_integer(999, ""), "999");
+        assert_eq!(format.format_integer(1000, ""), "1,000");
+        assert_eq!(
+            format.format_integer(12_345_678, " frames"),
+            "12,345,678 frames"
+        );
+        assert_eq!(format.format(1234.5, "ms"), "1,234.50ms");
+        assert_eq!(format.format(-1234.5, ""), "-1,234.50");
+        assert_eq!(format.format(123.0, ""), "123.00");
+    }
+
+    #[test]
+    fn non_finite_values() {
+        let format = NumberFormat::default();
+        assert_eq!(format.format(f64::NAN, ""), "NaN");
+        assert_eq!(format.format(f64::INFINITY, " fps"), "inf fps");
+        assert_eq!(format.format(f64::NEG_INFINITY, ""), "-inf");
+    }
+
+    #[test]
+    fn units() {
+        let format = NumberFormat {
+            decimals: 1,
+            units: NumberUnits {
+                fps: "FPS".to_string(),
+                milliseconds: " ms".to_string(),
+                seconds: " sec".to_string(),
+            },
+            ..NumberFormat::default()
+        };
+        assert_eq!(format.format_fps(60.0), "60.0FPS");
+        assert_eq!(format.format_ms(0.0166), "16.6 ms");
+        assert_eq!(format.format_seconds(12.34), "12.3 sec");
+
+        let format = NumberFormat::default();
+        assert_eq!(format.format_fps(60.0), "60.00 fps");
+        assert_eq!(format.format_ms(0.016), "16.00ms");
+        assert_eq!(format.format_seconds(1.5), "1.50s");
+    }
+
+    #[test]
+    fn glyphs_of_the_text() {
+        assert_eq!(glyphs("0a ").collect::<Vec<_>>(), vec![48, 97, 32]);
+        assert_eq!(glyphs("µs").collect::<Vec<_>>(), vec![181, 115]);
+        assert_eq!(glyphs("→").collect::<Vec<_>>(), vec![REPLACEMENT_GLYPH]);
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::{prelude::*, render::render_resource::ShaderType};
+
+use crate::{Frametimes, OverlayConfig, OverlayRow};
+
+/// The maximum amount of bins of the histogram
+pub const MAX_HISTOGRAM_BINS: usize = 32;
+
+/// How the bins of the histogram are spread between the first and last [`OverlayConfig::dts`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, FromReflect)]
+pub enum BinSpacing {
+    /// Every bin covers the same range of delta times
+    Linear,
+    /// Every bin covers the same range of log2(delta time), like the bars of the graph
+    #[default]
+    Log,
+}
+
+/// Configures the histogram of the recorded frametimes
+#[derive(Debug, Clone, Reflect, FromReflect)]
+pub struct HistogramConfig {
+    /// The amount of bins, clamped to [`MAX_HISTOGRAM_BINS`]
+    ///
+    /// Defaults to 16
+    pub bins: usize,
+    /// Defaults to [`BinSpacing::Log`]
+    pub spacing: BinSpacing,
+}
+
+impl Default for HistogramConfig {
+    fn default() -> Self {
+        Self {
+            bins: 16,
+            spacing: BinSpacing::Log,
+        }
+    }
+}
+
+/// The amount of recorded frametimes in each bin of the histogram shown by [`OverlayRow::Histogram`]
+///
+/// Frametimes outside of the range of the histogram are counted in the first or last bin.
+#[derive(Debug, Clone, ShaderType, Resource)]
+pub struct Histogram {
+    /// 0 when the histogram isn't shown
+    pub bin_count: u32,
+    /// The highest count of all the bins
+    pub max_count: f32,
+    pub counts: [f32; MAX_HISTOGRAM_BINS],
+    /// The delta time at the center of each bin, used for its color
+    pub centers: [f32; MAX_HISTOGRAM_BINS],
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            bin_count: 0,
+            max_count: 0.0,
+            counts: [0.0; MAX_HISTOGRAM_BINS],
+            centers: [0.0; MAX_HISTOGRAM_BINS],
+        }
+    }
+}
+
+impl Histogram {
+    fn new(config: &HistogramConfig, dts: Vec4, frametimes: impl IntoIterator<Item = f32>) -> Self {
+        let bin_count = config.bins.clamp(1, MAX_HISTOGRAM_BINS);
+        let (min, max) = match config.spacing {
+            BinSpacing::Linear => (dts[0], dts[3]),
+            BinSpacing::Log => (dts[0].log2(), dts[3].log2()),
+        };
+        let to_scale = |dt: f32| match config.spacing {
+            BinSpacing::Linear => dt,
+            BinSpacing::Log => dt.log2(),
+        };
+        let from_scale = |value: f32| match config.spacing {
+            BinSpacing::Linear => value,
+            BinSpacing::Log => value.exp2(),
+        };
+
+        let mut histogram = Self {
+            bin_count: bin_count as u32,
+            ..default()
+        };
+        let bin_size = (max - min) / bin_count as f32;
+        for (i, center) in histogram.centers[..bin_count].iter_mut().enumerate() {
+            *center = from_scale(min + bin_size * (i as f32 + 0.5));
+        }
+
+        for dt in frametimes {
+            let bin = ((to_scale(dt) - min) / bin_size).floor();
+            let bin = (bin.max(0.0) as usize).min(bin_count - 1);
+            histogram.counts[bin] += 1.0;
+        }
+        histogram.max_count = histogram.counts.iter().copied().fold(0.0, f32::max);
+        histogram
+    }
+}
+
+pub(crate) fn prepare_histogram(
+    config: Res<OverlayConfig>,
+    frametimes: Res<Frametimes>,
+    mut histogram: ResMut<Histogram>,
+) {
+    if !frametimes.is_changed() && !config.is_changed() {
+        return;
+    }
+
+    *histogram = if config.shown_rows().contains(&OverlayRow::Histogram) {
+        Histogram::new(
+            &config.histogram,
+            config.dts,
+            frametimes.recorded(config.graph_start()),
+        )
+    } else {
+        Histogram::default()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bins: usize, spacing: BinSpacing) -> HistogramConfig {
+        HistogramConfig { bins, spacing }
+    }
+
+    #[test]
+    fn linear_bins() {
+        let dts = Vec4::new(0.01, 0.02, 0.03, 0.05);
+        let histogram = Histogram::new(
+            &config(4, BinSpacing::Linear),
+            dts,
+            [0.015, 0.025, 0.026, 0.045],
+        );
+        assert_eq!(histogram.bin_count, 4);
+        assert_eq!(histogram.counts[..4], [1.0, 2.0, 0.0, 1.0]);
+        assert_eq!(histogram.max_count, 2.0);
+        for (center, expected) in histogram.centers[..4]
+            .iter()
+            .zip([0.015, 0.025, 0.035, 0.045])
+        {
+            assert!((center - expected).abs() < 1e-6, "{center} != {expected}");
+        }
+    }
+
+    #[test]
+    fn log_bins() {
+        // Every bin covers a doubling of the delta time
+        let dts = Vec4::new(0.004, 0.008, 0.016, 0.064);
+        let histogram = Histogram::new(
+            &config(4, BinSpacing::Log),
+            dts,
+            [0.005, 0.01, 0.02, 0.03, 0.05],
+        );
+        assert_eq!(histogram.counts[..4], [1.0, 1.0, 2.0, 1.0]);
+        assert!((histogram.centers[0] - 0.004 * 2f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn out_of_range_frames_are_clamped() {
+        let dts = Vec4::new(0.01, 0.02, 0.03, 0.05);
+        for spacing in [BinSpacing::Linear, BinSpacing::Log] {
+            let histogram = Histogram::new(&config(4, spacing), dts, [0.001, 0.01, 0.05, 1.0]);
+            assert_eq!(histogram.counts[..4], [2.0, 0.0, 0.0, 2.0]);
+        }
+    }
+
+    #[test]
+    fn unrecorded_frames_are_ignored() {
+        let dts = Vec4::new(0.01, 0.02, 0.03, 0.05);
+        let histogram = Histogram::new(&config(4, BinSpacing::Log), dts, []);
+        assert_eq!(histogram.bin_count, 4);
+        assert_eq!(histogram.max_count, 0.0);
+        assert!(histogram.counts.iter().all(|count| *count == 0.0));
+
+        // The start of the buffer is zeroed until enough frames are recorded
+        let mut frametimes = Frametimes::default();
+        frametimes.push(0.015);
+        frametimes.push(0.045);
+        let histogram = Histogram::new(&config(4, BinSpacing::Linear), dts, frametimes.recorded(0));
+        assert_eq!(histogram.counts[..4], [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn only_the_frames_of_the_graph_are_counted() {
+        let config = OverlayConfig {
+            buffer_len: 4,
+            ..default()
+        };
+        let mut frametimes = Frametimes::default();
+        for _ in 0..10 {
+            frametimes.push(0.016);
+        }
+        let histogram = Histogram::new(
+            &config.histogram,
+            config.dts,
+            frametimes.recorded(config.graph_start()),
+        );
+        assert_eq!(histogram.counts.iter().sum::<f32>(), 4.0);
+    }
+}
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use bevy::{
+    core::FrameCount,
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use crate::{
+    FrameStat, Frametimes, GraphSeriesValues, OverlayConfig, OverlayState, FRAMETIME_BUFFER_LEN,
+    MAX_GRAPH_SERIES,
+};
+
+/// A frame recorded by the overlay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, FromReflect)]
+pub struct FrameSample {
+    /// The frametime in seconds
+    pub frametime: f32,
+    /// The [`FrameCount`] of the frame
+    pub frame: u32,
+    /// The time elapsed since the startup of the app when the frame was recorded
+    pub timestamp: Duration,
+    /// The value of each [`crate::GraphSeries`] in seconds
+    pub series: [f32; MAX_GRAPH_SERIES],
+}
+
+/// The last [`FRAMETIME_BUFFER_LEN`] frames recorded in the main world
+///
+/// Use it to react to the performance of the app, like lowering the quality settings when the
+/// frames take too long. The frames are always recorded, even when the overlay is disabled or frozen.
+/// The render world extracts the frames displayed in the graph from it.
+///
+/// All the durations are in seconds.
+#[derive(Debug, Default, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct FrameHistory {
+    // A Vec since VecDeque can't be reflected, removing the oldest of the few frames is cheap
+    recorded: Vec<FrameSample>,
+    /// The frames shown in the graph, oldest first
+    pub(crate) displayed: Vec<FrameSample>,
+}
+
+impl FrameHistory {
+    pub(crate) fn push(&mut self, sample: FrameSample, frozen: bool) {
+        if self.recorded.len() == FRAMETIME_BUFFER_LEN {
+            self.recorded.remove(0);
+        }
+        self.recorded.push(sample);
+
+        if !frozen {
+            self.displayed.clone_from(&self.recorded);
+        }
+    }
+
+    /// The recorded frames, oldest first
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &FrameSample> + ExactSizeIterator {
+        self.recorded.iter()
+    }
+
+    /// The frametimes of the recorded frames, oldest first
+    pub fn frametimes(&self) -> impl DoubleEndedIterator<Item = f32> + ExactSizeIterator + '_ {
+        self.recorded.iter().map(|sample| sample.frametime)
+    }
+
+    /// The most recent frame
+    pub fn latest(&self) -> Option<&FrameSample> {
+        self.recorded.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.recorded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recorded.is_empty()
+    }
+
+    /// The average frametime of the recorded frames
+    pub fn average(&self) -> Option<f32> {
+        self.stat(FrameStat::Average)
+    }
+
+    /// The shortest frametime of the recorded frames
+    pub fn min(&self) -> Option<f32> {
+        self.stat(FrameStat::Min)
+    }
+
+    /// The longest frametime of the recorded frames
+    pub fn max(&self) -> Option<f32> {
+        self.stat(FrameStat::Max)
+    }
+
+    /// Computes a [`FrameStat`] of the recorded frames
+    pub fn stat(&self, stat: FrameStat) -> Option<f32> {
+        stat.compute(self.frametimes())
+    }
+
+    /// The frametime that the given fraction of the recorded frames don't exceed, like 0.99 for the
+    /// 99th percentile, using the nearest rank
+    pub fn percentile(&self, fraction: f32) -> Option<f32> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut frametimes: Vec<f32> = self.frametimes().collect();
+        frametimes.sort_by(f32::total_cmp);
+        let rank = (fraction.clamp(0.0, 1.0) * frametimes.len() as f32).ceil() as usize;
+        Some(frametimes[rank.saturating_sub(1)])
+    }
+
+    /// The frame rate matching the average frametime of the recorded frames
+    pub fn average_fps(&self) -> Option<f32> {
+        self.average()
+            .filter(|average| *average > 0.0)
+            .map(|average| 1.0 / average)
+    }
+
+    /// Gets the displayed frame at the given index of the buffers uploaded to the gpu
+    ///
+    /// The buffers are filled from the end, so the start of the buffers is empty until enough frames are recorded.
+    pub(crate) fn get_displayed(&self, buffer_index: usize) -> Option<&FrameSample> {
+        let empty = FRAMETIME_BUFFER_LEN - self.displayed.len();
+        buffer_index
+            .checked_sub(empty)
+            .and_then(|i| self.displayed.get(i))
+    }
+
+    pub(crate) fn displayed_frametimes(&self) -> Frametimes {
+        let mut frametimes = Frametimes::default();
+        for sample in &self.displayed {
+            frametimes.push(sample.frametime);
+        }
+        frametimes
+    }
+
+    pub(crate) fn displayed_series_values(&self) -> GraphSeriesValues {
+        let mut series_values = GraphSeriesValues::default();
+        for sample in &self.displayed {
+            for (i, value) in sample.series.iter().enumerate() {
+                series_values.push(i, *value);
+            }
+        }
+        series_values
+    }
+}
+
+pub(crate) fn record_frame(
+    diagnostics: Res<Diagnostics>,
+    config: Res<OverlayConfig>,
+    frame_count: Res<FrameCount>,
+    time: Res<Time>,
+    state: Res<OverlayState>,
+    mut history: ResMut<FrameHistory>,
+) {
+    let dt = match diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.value())
+    {
+        Some(dt) => dt,
+        None => return,
+    };
+
+    let mut series = [0.0; MAX_GRAPH_SERIES];
+    for (value, series_config) in series.iter_mut().zip(config.series.iter()) {
+        if let Some(diagnostic_value) = diagnostics
+            .get(series_config.diagnostic)
+            .and_then(|diagnostic| diagnostic.value())
+        {
+            *value = diagnostic_value as f32 / 1000.0;
+        }
+    }
+
+    // The diagnostics are in ms
+    let sample = FrameSample {
+        frametime: dt as f32 / 1000.0,
+        frame: frame_count.0,
+        timestamp: time.elapsed(),
+        series,
+    };
+    history.push(sample, state.frozen);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(frametimes: &[f32]) -> FrameHistory {
+        let mut history = FrameHistory::default();
+        for &frametime in frametimes {
+            let sample = FrameSample {
+                frametime,
+                ..default()
+            };
+            history.push(sample, false);
+        }
+        history
+    }
+
+    #[test]
+    fn percentile_bounds() {
+        let history = history(&[0.02, 0.04, 0.01, 0.03]);
+        assert_eq!(history.percentile(0.0), Some(0.01));
+        assert_eq!(history.percentile(1.0), Some(0.04));
+    }
+
+    #[test]
+    fn percentile_single_sample() {
+        let history = history(&[0.016]);
+        assert_eq!(history.percentile(0.0), Some(0.016));
+        assert_eq!(history.percentile(0.5), Some(0.016));
+        assert_eq!(history.percentile(1.0), Some(0.016));
+    }
+
+    #[test]
+    fn percentile_empty() {
+        assert_eq!(FrameHistory::default().percentile(0.5), None);
+    }
+
+    #[test]
+    fn stats() {
+        let history = history(&[0.02, 0.04, 0.01, 0.03]);
+        assert_eq!(history.average(), Some(0.025));
+        assert_eq!(history.min(), Some(0.01));
+        assert_eq!(history.max(), Some(0.04));
+        assert_eq!(FrameStat::Average.compute([]), None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{borrow::Cow, fmt, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{keyed_text::KeyedText, FrameHistory, OverlayConfig};
+
+/// Configures the warning logged when a hitch is detected
+#[derive(Debug, Clone, Reflect, FromReflect)]
+pub struct HitchLogConfig {
+    /// The minimum time between two log records.
+    /// Hitches detected in that window are only counted and reported with the next record.
+    ///
+    /// Defaults to 1 second
+    pub min_interval: Duration,
+}
+
+impl Default for HitchLogConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Sent every time a frame takes longer than [`OverlayConfig::hitch_threshold`]
+///
+/// All the durations are in seconds
+#[derive(Debug, Clone)]
+pub struct FrameHitch {
+    pub frame: u32,
+    pub frametime: f32,
+    /// Average frametime of the recent frames, including the hitch
+    pub average: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// User supplied fields added to every hitch log record
+///
+/// Use it to record what the app was doing, like the current level or the amount of entities.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct HitchContext {
+    fields: KeyedText,
+}
+
+impl HitchContext {
+    /// Sets the value of a field, replacing the previous value if the field already exists
+    pub fn set(&mut self, key: impl Into<Cow<'static, str>>, value: impl ToString) {
+        self.fields.set(key, value);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.fields.remove(key);
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
+
+impl fmt::Display for HitchContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.fields, f)
+    }
+}
+
+pub(crate) fn detect_hitches(
+    history: Res<FrameHistory>,
+    config: Res<OverlayConfig>,
+    mut hitches: EventWriter<FrameHitch>,
+) {
+    // Only check each frame once
+    if !history.is_changed() {
+        return;
+    }
+    let latest = match history.latest() {
+        Some(latest) => latest,
+        None => return,
+    };
+    if latest.frametime <= config.hitch_threshold {
+        return;
+    }
+
+    hitches.send(FrameHitch {
+        frame: latest.frame,
+        frametime: latest.frametime,
+        average: history.average().unwrap_or(latest.frametime),
+        min: history.min().unwrap_or(latest.frametime),
+        max: history.max().unwrap_or(latest.frametime),
+    });
+}
+
+#[derive(Default)]
+pub(crate) struct HitchLogState {
+    last_log: Option<Duration>,
+    suppressed: u32,
+}
+
+pub(crate) fn log_hitches(
+    config: Res<OverlayConfig>,
+    context: Res<HitchContext>,
+    time: Res<Time>,
+    mut hitches: EventReader<FrameHitch>,
+    mut state: Local<HitchLogState>,
+) {
+    for hitch in hitches.iter() {
+        // Events still need to be consumed when logging is disabled
+        let log_config = match &config.hitch_log {
+            Some(log_config) => log_config,
+            None => continue,
+        };
+
+        let now = time.elapsed();
+        if let Some(last_log) = state.last_log {
+            if now.saturating_sub(last_log) < log_config.min_interval {
+                state.suppressed += 1;
+                continue;
+            }
+        }
+
+        warn!(
+            frame = hitch.frame,
+            frametime_ms = hitch.frametime * 1000.,
+            average_ms = hitch.average * 1000.,
+            min_ms = hitch.min * 1000.,
+            max_ms = hitch.max * 1000.,
+            suppressed = state.suppressed,
+            context = %*context,
+            "Frame hitch detected"
+        );
+        state.last_log = Some(now);
+        state.suppressed = 0;
+    }
+}
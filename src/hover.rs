@@ -0,0 +1,136 @@
+use bevy::{prelude::*, render::camera::RenderTarget, window::WindowId};
+
+use crate::{
+    CameraOverlay, FrameHistory, FrameSample, OverlayConfig, OverlayState, FRAMETIME_BUFFER_LEN,
+    OVERLAY_WIDTH, ROW_HEIGHT,
+};
+
+/// The frame of the graph under the mouse cursor
+///
+/// Only frames of an enabled overlay drawn on the primary window can be hovered.
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct HoveredFrame {
+    pub sample: Option<FrameSample>,
+    /// The index of the frame in the buffers uploaded to the gpu
+    pub(crate) buffer_index: usize,
+}
+
+/// Finds the frame drawn at the given horizontal position of the graph, from 0 to 1
+///
+/// This needs to match the layout of the bars in the shader
+fn frame_at(config: &OverlayConfig, history: &FrameHistory, x: f32) -> Option<usize> {
+    let len = config.graph_len();
+    let bar_count = config.bar_count();
+    // With fixed width bars, only the most recent frames are shown
+    let first_frame = bar_count.map_or(config.graph_start(), |count| FRAMETIME_BUFFER_LEN - count);
+
+    let frametimes = history.displayed_frametimes();
+    let mut graph_width = 0.0;
+    for (i, dt) in frametimes.values.iter().enumerate().skip(first_frame) {
+        let frame_width = match bar_count {
+            Some(count) => 1.0 / count as f32,
+            None => (dt / config.dts[0]) / len as f32,
+        };
+        if x >= graph_width && x < graph_width + frame_width {
+            return Some(i);
+        }
+        graph_width += frame_width;
+    }
+    None
+}
+
+pub(crate) fn update_hovered_frame(
+    windows: Res<Windows>,
+    config: Res<OverlayConfig>,
+    history: Res<FrameHistory>,
+    state: Res<OverlayState>,
+    cameras: Query<&Camera, With<CameraOverlay>>,
+    mut hovered: ResMut<HoveredFrame>,
+) {
+    // The cursor is only mapped to the overlay drawn in the top left corner of the primary window,
+    // not to an overlay drawn in an image or in another window
+    let drawn_on_primary_window = cameras.iter().any(|camera| {
+        camera.is_active
+            && matches!(camera.target, RenderTarget::Window(id) if id == WindowId::primary())
+    });
+
+    // The cursor position is in logical pixels from the bottom left of the window,
+    // but the overlay is laid out from the top left and scaled by the layout scale
+    let window = windows
+        .get_primary()
+        .filter(|_| state.enabled && drawn_on_primary_window);
+    let cursor = window.and_then(|window| {
+        let position = window.cursor_position()?;
+        let scale = window.scale_factor() as f32;
+        let physical = Vec2::new(
+            position.x * scale,
+            window.physical_height() as f32 - position.y * scale,
+        );
+        Some(physical / config.layout_scale(scale))
+    });
+
+    let padding = Vec2::splat(config.theme.padding);
+    let buffer_index = match (cursor, config.graph_row()) {
+        (Some(cursor), Some(graph_row)) => {
+            let cursor = cursor - padding;
+            let graph_top = graph_row as f32 * ROW_HEIGHT;
+            if cursor.y >= graph_top && cursor.y < graph_top + ROW_HEIGHT {
+                frame_at(&config, &history, cursor.x / OVERLAY_WIDTH)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    let sample = buffer_index.and_then(|i| history.get_displayed(i)).copied();
+
+    if hovered.sample != sample {
+        hovered.sample = sample;
+        hovered.buffer_index = buffer_index.unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BarWidth;
+
+    #[test]
+    fn short_graph_hovers_the_most_recent_frames() {
+        let mut history = FrameHistory::default();
+        for frame in 0..10 {
+            let sample = FrameSample {
+                frametime: 0.016,
+                frame,
+                ..default()
+            };
+            history.push(sample, false);
+        }
+        let config = OverlayConfig {
+            buffer_len: 4,
+            bar_width: BarWidth::Fixed(2),
+            ..default()
+        };
+        // The left bar is the second most recent frame
+        let index = frame_at(&config, &history, 0.25).unwrap();
+        assert_eq!(index, FRAMETIME_BUFFER_LEN - 2);
+        assert_eq!(
+            history.get_displayed(index).map(|sample| sample.frame),
+            Some(8)
+        );
+
+        let config = OverlayConfig {
+            buffer_len: 4,
+            bar_width: BarWidth::Variable,
+            dts: Vec4::splat(0.016),
+            ..config
+        };
+        // Each frame takes a quarter of the graph
+        let index = frame_at(&config, &history, 0.1).unwrap();
+        assert_eq!(
+            history.get_displayed(index).map(|sample| sample.frame),
+            Some(6)
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::{borrow::Cow, fmt};
+
+/// Text values identified by a key, kept in the order they were first set
+#[derive(Debug, Default, Clone)]
+pub(crate) struct KeyedText {
+    entries: Vec<(Cow<'static, str>, String)>,
+}
+
+impl KeyedText {
+    /// Sets the text of a key, replacing the previous text if the key already exists
+    pub(crate) fn set(&mut self, key: impl Into<Cow<'static, str>>, text: impl ToString) {
+        let key = key.into();
+        let text = text.to_string();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, t)) => *t = text,
+            None => self.entries.push((key, text)),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, text)| text.as_str())
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        self.entries.retain(|(k, _)| k != key);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Formats the entries as `key=text` separated by spaces
+impl fmt::Display for KeyedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, text)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}={text}")?;
+        }
+        Ok(())
+    }
+}
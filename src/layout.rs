@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::keyed_text::KeyedText;
+
+/// The maximum amount of rows of the overlay, the hover tooltip is drawn after them
+pub const MAX_OVERLAY_ROWS: usize = 16;
+
+/// The kind of the single row of the compact mode, it needs to match the shader
+pub(crate) const COMPACT_ROW_KIND: u32 = 4;
+
+/// A statistic of the recorded frametimes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub enum FrameStat {
+    Average,
+    Min,
+    Max,
+}
+
+impl FrameStat {
+    /// Computes the statistic of the given frametimes, `None` when there are none
+    pub(crate) fn compute(self, frametimes: impl IntoIterator<Item = f32>) -> Option<f32> {
+        let frametimes = frametimes.into_iter();
+        match self {
+            FrameStat::Average => {
+                let (sum, count) =
+                    frametimes.fold((0.0, 0), |(sum, count), dt| (sum + dt, count + 1));
+                (count > 0).then(|| sum / count as f32)
+            }
+            FrameStat::Min => frametimes.reduce(f32::min),
+            FrameStat::Max => frametimes.reduce(f32::max),
+        }
+    }
+}
+
+/// A row of the overlay, see [`crate::OverlayConfig::rows`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub enum OverlayRow {
+    Fps,
+    /// The frametime of the last frame in ms
+    FrameTime,
+    /// The amount of frames since the start of the app
+    FrameCount,
+    /// The resolution and scale factor of the target the overlay is drawn on
+    Resolution,
+    /// A statistic of the frametimes shown in the graph
+    Stats(FrameStat),
+    /// The average frame to frame delta and the amount of deviating frames, see [`crate::FramePacing`]
+    Pacing,
+    /// The frametime graph
+    Graph,
+    /// The frame to frame delta of each frame, drawn with the same layout as the frametime graph
+    PacingGraph,
+    /// A histogram of the recorded frametimes configured by [`crate::OverlayConfig::histogram`]
+    Histogram,
+    /// The text set for this key in [`CustomRows`]
+    Custom(Cow<'static, str>),
+}
+
+impl OverlayRow {
+    /// The rows shown by default
+    pub fn default_rows() -> Vec<OverlayRow> {
+        vec![
+            OverlayRow::Fps,
+            OverlayRow::FrameTime,
+            OverlayRow::FrameCount,
+            OverlayRow::Resolution,
+            OverlayRow::Graph,
+        ]
+    }
+
+    /// How the shader draws the row, it needs to match the shader
+    pub(crate) fn kind(&self) -> u32 {
+        match self {
+            OverlayRow::Graph => 1,
+            OverlayRow::PacingGraph => 2,
+            OverlayRow::Histogram => 3,
+            // Every other row is text
+            _ => 0,
+        }
+    }
+}
+
+/// The text of the [`OverlayRow::Custom`] rows
+///
+/// Use it to show app specific values, like the amount of entities or the current level.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct CustomRows {
+    rows: KeyedText,
+}
+
+impl CustomRows {
+    /// Sets the text of a row, replacing the previous text if the row already exists
+    pub fn set(&mut self, key: impl Into<Cow<'static, str>>, text: impl ToString) {
+        self.rows.set(key, text);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.rows.get(key)
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.rows.remove(key);
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+}
@@ -1,31 +1,67 @@
 #![allow(clippy::too_many_arguments)]
 
+mod font;
+mod format;
+mod histogram;
+mod history;
+mod hitch;
+mod hover;
+mod keyed_text;
+mod layout;
 mod overlay_node;
+mod pacing;
+mod persistence;
 mod pipeline;
+mod settings;
+mod text;
+mod theme;
+mod view;
+
+use std::path::PathBuf;
 
 use bevy::{
     core::FrameCount,
-    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    core_pipeline::{core_2d, core_3d},
+    diagnostic::{DiagnosticId, Diagnostics, FrameTimeDiagnosticsPlugin},
     ecs::query::QueryItem,
     prelude::*,
     render::{
-        camera::CameraRenderGraph,
+        camera::{CameraRenderGraph, RenderTarget},
         extract_component::{ExtractComponent, ExtractComponentPlugin},
         render_asset::RenderAssets,
         render_graph::{RenderGraph, SlotInfo, SlotType},
-        render_resource::{OwnedBindingResource, ShaderType, StorageBuffer, UniformBuffer},
+        render_resource::{
+            Extent3d, OwnedBindingResource, ShaderType, StorageBuffer, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureUsages, UniformBuffer,
+        },
         renderer::{RenderDevice, RenderQueue},
-        texture::{FallbackImage, GpuImage},
+        texture::{BevyDefault, FallbackImage, GpuImage},
         view::VisibleEntities,
         Extract, RenderApp, RenderStage,
     },
+    ui::draw_ui_graph,
 };
+use serde::{Deserialize, Serialize};
 
+use font::{FontImage, GlyphAdvances};
 use overlay_node::{graph, OverlayNode};
+use persistence::OverlayPersistence;
 use pipeline::OverlayPipeline;
+use view::{OverlayViewBuffers, WindowScaleFactors};
+
+pub use font::{OverlayFont, TextRendering};
+pub use format::{NumberFormat, NumberUnits};
+pub use histogram::{BinSpacing, Histogram, HistogramConfig, MAX_HISTOGRAM_BINS};
+pub use history::{FrameHistory, FrameSample};
+pub use hitch::{FrameHitch, HitchContext, HitchLogConfig};
+pub use hover::HoveredFrame;
+pub use layout::{CustomRows, FrameStat, OverlayRow, MAX_OVERLAY_ROWS};
+pub use pacing::{FramePacing, PacingConfig};
+pub use settings::{OverlaySettings, OverlaySettingsLoader};
+pub use text::{OverlayText, TextLine, MAX_ROW_GLYPHS, MAX_TEXT_ROWS};
+pub use theme::OverlayTheme;
 
 // TODO show gpu and cpu information
-// TODO show avg, min, max frametime
 // TODO show vsync option
 // TODO display toggle
 // TODO stabilize FPS values
@@ -37,9 +73,164 @@ use pipeline::OverlayPipeline;
 // TODO make this configurable
 pub const FRAMETIME_BUFFER_LEN: usize = 64;
 
+/// The width of the overlay in pixels before applying the layout scale, it needs to match the shader
+pub(crate) const OVERLAY_WIDTH: f32 = 175.0;
+/// The height of a row of the overlay in pixels before applying the layout scale, it needs to match the shader
+pub(crate) const ROW_HEIGHT: f32 = 1.3 * 16.0;
+
+/// The maximum amount of [`BudgetLine`] drawn over the frametime graph
+pub const MAX_BUDGET_LINES: usize = 4;
+
+/// The maximum amount of [`GraphSeries`] drawn over the frametime graph
+pub const MAX_GRAPH_SERIES: usize = 4;
+
+/// A horizontal reference line drawn across the frametime graph
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct BudgetLine {
+    /// The delta time where the line is drawn.
+    /// It uses the same scale as the bars of the graph.
+    pub dt: f32,
+    pub color: Color,
+    /// Prints the delta time of the line in ms at the start of the line
+    pub label: bool,
+}
+
+impl BudgetLine {
+    pub fn new(dt: f32, color: Color) -> Self {
+        Self {
+            dt,
+            color,
+            label: true,
+        }
+    }
+
+    /// Creates a line at the frame budget of the given frame rate
+    pub fn from_fps(fps: f32, color: Color) -> Self {
+        Self::new(1. / fps, color)
+    }
+}
+
+/// How the height of the bars is computed from their delta time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Reflect, FromReflect)]
+pub enum GraphScale {
+    /// Logarithmic scale between the first and last [`OverlayConfig::dts`]
+    #[default]
+    Log2,
+    /// Linear scale between the given delta times, the bounds are swapped when `min` is greater than `max`
+    Linear { min: f32, max: f32 },
+    /// Linear scale that adapts to the min and max of the recent delta times.
+    ///
+    /// The range grows as soon as a frametime doesn't fit, but only shrinks slowly once the frametimes
+    /// are stable, to avoid jumping around.
+    Auto,
+}
+
+impl GraphScale {
+    fn as_u32(&self) -> u32 {
+        match self {
+            GraphScale::Log2 => 0,
+            GraphScale::Linear { .. } => 1,
+            GraphScale::Auto => 2,
+        }
+    }
+}
+
+/// How the width of the bars is computed
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect,
+)]
+pub enum BarWidth {
+    /// The width of a bar is proportional to its delta time, so slow frames take more space.
+    /// The amount of visible frames depends on the performance.
+    #[default]
+    Variable,
+    /// Every bar has the same width and the graph shows exactly this amount of the most recent frames.
+    /// It is clamped to [`OverlayConfig::buffer_len`].
+    Fixed(usize),
+}
+
+/// How the frametime history is drawn
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect,
+)]
+pub enum GraphStyle {
+    /// A bar per frame
+    #[default]
+    Bars,
+    /// A line going through the top of each frame
+    Line,
+    /// A line with the area under it filled
+    Area,
+}
+
+impl GraphStyle {
+    fn as_u32(&self) -> u32 {
+        match self {
+            GraphStyle::Bars => 0,
+            GraphStyle::Line => 1,
+            GraphStyle::Area => 2,
+        }
+    }
+}
+
+/// Presets for [`OverlayConfig::colors`]
+///
+/// Except for the default one, the palettes don't rely on telling red and green apart.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect,
+)]
+pub enum ColorPalette {
+    /// Green, yellow, orange, red
+    #[default]
+    Default,
+    /// Blue to yellow, based on the viridis color map
+    Viridis,
+    /// Dark blue to yellow, based on the cividis color map
+    Cividis,
+    /// Blue to orange, from the Okabe-Ito palette
+    BlueOrange,
+}
+
+impl ColorPalette {
+    /// The colors of the palette from the fastest to the slowest delta time
+    pub fn colors(&self) -> [Color; 4] {
+        match self {
+            ColorPalette::Default => [Color::GREEN, Color::YELLOW, Color::ORANGE, Color::RED],
+            ColorPalette::Viridis => [
+                Color::rgb_u8(59, 82, 139),
+                Color::rgb_u8(33, 145, 140),
+                Color::rgb_u8(94, 201, 98),
+                Color::rgb_u8(253, 231, 37),
+            ],
+            ColorPalette::Cividis => [
+                Color::rgb_u8(65, 77, 107),
+                Color::rgb_u8(124, 123, 120),
+                Color::rgb_u8(188, 175, 111),
+                Color::rgb_u8(254, 232, 56),
+            ],
+            ColorPalette::BlueOrange => [
+                Color::rgb_u8(0, 114, 178),
+                Color::rgb_u8(86, 180, 233),
+                Color::rgb_u8(230, 159, 0),
+                Color::rgb_u8(213, 94, 0),
+            ],
+        }
+    }
+}
+
+/// An additional diagnostic drawn as a line over the frametime graph, like the cpu or gpu time of a frame
+///
+/// It uses the same layout and scale as the frametimes so the values can be compared.
+#[derive(Debug, Clone)]
+pub struct GraphSeries {
+    /// The diagnostic needs to be measured in ms, like [`FrameTimeDiagnosticsPlugin::FRAME_TIME`]
+    pub diagnostic: DiagnosticId,
+    pub color: Color,
+}
+
 // TODO use a struct containing each pair of dt and color
-// TODO support runtime config
-#[derive(Debug, Clone, Resource)]
+#[derive(Debug, Clone, Resource, Reflect)]
+#[reflect(Resource)]
 pub struct OverlayConfig {
     /// The list of delta times where the colors will change in order of smallest to biggest.
     ///
@@ -49,10 +240,100 @@ pub struct OverlayConfig {
     /// Since the bars aren't all of the same size, this is the maximum value possible
     pub buffer_len: usize,
     /// The colors used in the overlay.
+    /// Use [`ColorPalette::colors`] for a colorblind safe palette.
     ///
     /// Defaults to green, yellow, orange, red
-    pub colors: Mat4,
+    pub colors: [Color; 4],
+    /// Draws stripes over the bars of the frames taking longer than this many seconds,
+    /// so the frames over budget can be told apart without relying on their color.
+    ///
+    /// Disabled by default
+    pub hatch_threshold: Option<f32>,
+    /// An atlas replacing the default font, laid out in a grid of 16x16 cells like `assets/font.png`.
+    /// Every glyph advances by half a cell, see [`TextRendering`] for how the coverage of the glyphs is read.
+    ///
+    /// Defaults to None
     pub font_handle: Option<Handle<Image>>,
+    /// A TTF or OTF font rasterized into an atlas once loaded, with proportional advances.
+    /// It takes priority over [`OverlayConfig::font_handle`].
+    ///
+    /// Defaults to None
+    pub font: Option<OverlayFont>,
+    /// Defaults to [`TextRendering::Sdf`]
+    pub text_rendering: TextRendering,
+    /// The frametime in seconds above which a frame is considered a hitch and a [`FrameHitch`] is sent.
+    ///
+    /// Defaults to 1/15
+    pub hitch_threshold: f32,
+    /// Logs a warning with the context of the hitch every time one is detected.
+    ///
+    /// Disabled by default
+    pub hitch_log: Option<HitchLogConfig>,
+    /// Reference lines drawn across the frametime graph, like the target frame budget.
+    /// Only the first [`MAX_BUDGET_LINES`] are drawn.
+    pub budget_lines: Vec<BudgetLine>,
+    /// The vertical scale of the frametime graph
+    ///
+    /// Defaults to [`GraphScale::Log2`]
+    pub scale: GraphScale,
+    /// The width of the bars of the frametime graph
+    ///
+    /// Defaults to [`BarWidth::Variable`]
+    pub bar_width: BarWidth,
+    /// How the frametime history is drawn
+    ///
+    /// Defaults to [`GraphStyle::Bars`]
+    pub graph_style: GraphStyle,
+    /// Smooths the edges of the lines and areas of the graph
+    ///
+    /// Defaults to true
+    pub antialiasing: bool,
+    /// Additional diagnostics drawn as lines over the frametime graph.
+    /// Only the first [`MAX_GRAPH_SERIES`] are drawn.
+    // The diagnostic ids can't be reflected
+    #[reflect(ignore)]
+    pub series: Vec<GraphSeries>,
+    /// The rows of the overlay from top to bottom. Only the first [`MAX_OVERLAY_ROWS`] are shown.
+    ///
+    /// Defaults to [`OverlayRow::default_rows`]
+    pub rows: Vec<OverlayRow>,
+    /// Configures the histogram shown by [`OverlayRow::Histogram`].
+    /// The bins use the same colors as the bars.
+    pub histogram: HistogramConfig,
+    /// Configures the [`FramePacing`] metrics
+    pub pacing: PacingConfig,
+    /// The key toggling [`OverlayState::frozen`]
+    ///
+    /// Defaults to F9
+    pub freeze_key: Option<KeyCode>,
+    /// Replaces the rows with a single line showing the fps and the frametime next to a small graph
+    /// of the recent frames. The text uses the colors of the frametime graph.
+    ///
+    /// Defaults to false
+    pub compact: bool,
+    /// The key toggling [`OverlayConfig::compact`]
+    ///
+    /// Defaults to F10
+    pub compact_key: Option<KeyCode>,
+    /// Colors the text of the [`OverlayRow::Fps`], [`OverlayRow::FrameTime`] and [`OverlayRow::Stats`]
+    /// rows like a bar of the same frametime, instead of using [`OverlayTheme::text_color`].
+    ///
+    /// Defaults to false
+    pub threshold_text_colors: bool,
+    /// The colors and spacing of the overlay
+    ///
+    /// Defaults to [`OverlayTheme::dark`]
+    pub theme: OverlayTheme,
+    /// How the numbers of the text rows and of the budget line labels are formatted
+    pub number_format: NumberFormat,
+    /// Scales the whole overlay on top of the scale factor of the window
+    ///
+    /// Defaults to 1.0
+    pub ui_scale: f32,
+    /// Settings loaded from a file that override this config once loaded and every time the file is modified.
+    ///
+    /// Defaults to None
+    pub settings: Option<Handle<OverlaySettings>>,
 }
 
 impl Default for OverlayConfig {
@@ -66,19 +347,192 @@ impl Default for OverlayConfig {
                 1. / 15.
             ),
             buffer_len: FRAMETIME_BUFFER_LEN,
-            colors: Mat4::from_cols_array_2d(&[
-                Color::GREEN.as_linear_rgba_f32(),
-                Color::YELLOW.as_linear_rgba_f32(),
-                Color::ORANGE.as_linear_rgba_f32(),
-                Color::RED.as_linear_rgba_f32(),
-            ]),
+            colors: ColorPalette::Default.colors(),
+            hatch_threshold: None,
             font_handle: None,
+            font: None,
+            text_rendering: TextRendering::Sdf,
+            hitch_threshold: 1. / 15.,
+            hitch_log: None,
+            budget_lines: vec![],
+            scale: GraphScale::Log2,
+            bar_width: BarWidth::Variable,
+            graph_style: GraphStyle::Bars,
+            antialiasing: true,
+            series: vec![],
+            rows: OverlayRow::default_rows(),
+            histogram: HistogramConfig::default(),
+            pacing: PacingConfig::default(),
+            freeze_key: Some(KeyCode::F9),
+            compact: false,
+            compact_key: Some(KeyCode::F10),
+            threshold_text_colors: false,
+            theme: OverlayTheme::dark(),
+            number_format: NumberFormat::default(),
+            ui_scale: 1.0,
+            settings: None,
+        }
+    }
+}
+
+impl OverlayConfig {
+    /// The rows that are shown, none in compact mode
+    pub(crate) fn shown_rows(&self) -> &[OverlayRow] {
+        if self.compact {
+            return &[];
+        }
+        &self.rows[..self.rows.len().min(MAX_OVERLAY_ROWS)]
+    }
+
+    /// The amount of frames drawn in the graph, [`OverlayConfig::buffer_len`] clamped to the size of the buffers
+    pub(crate) fn graph_len(&self) -> usize {
+        self.buffer_len.clamp(1, FRAMETIME_BUFFER_LEN)
+    }
+
+    /// The index of the oldest frame of the graph in the buffers uploaded to the gpu
+    ///
+    /// The buffers are filled from the end, so the graph shows the last [`OverlayConfig::graph_len`] frames.
+    pub(crate) fn graph_start(&self) -> usize {
+        FRAMETIME_BUFFER_LEN - self.graph_len()
+    }
+
+    /// The amount of bars of the graph with [`BarWidth::Fixed`], None when their width is variable
+    pub(crate) fn bar_count(&self) -> Option<usize> {
+        match self.bar_width {
+            BarWidth::Variable => None,
+            BarWidth::Fixed(count) => Some(count.clamp(1, self.graph_len())),
+        }
+    }
+
+    /// The index of the row of the frametime graph
+    pub(crate) fn graph_row(&self) -> Option<usize> {
+        self.shown_rows()
+            .iter()
+            .position(|row| *row == OverlayRow::Graph)
+    }
+
+    /// Whether the overlay can be drawn with the given [`OverlayConfig::ui_scale`]
+    pub(crate) fn is_valid_ui_scale(ui_scale: f32) -> bool {
+        ui_scale.is_finite() && ui_scale > 0.0
+    }
+
+    /// The amount of physical pixels per pixel of the layout of the overlay
+    pub(crate) fn layout_scale(&self, window_scale: f32) -> f32 {
+        (window_scale * self.ui_scale).max(f32::EPSILON)
+    }
+}
+
+/// The state of the overlay that can be changed at runtime
+#[derive(Debug, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct OverlayState {
+    /// Disabling the overlay despawns the overlay camera spawned by the plugin and stops drawing
+    /// the overlay in the render graphs. The frametimes are still recorded.
+    pub enabled: bool,
+    /// Stops the graph from scrolling so a hitch can be inspected.
+    /// The frametimes are still recorded in the background and the graph resumes with the
+    /// live values once unfrozen.
+    pub frozen: bool,
+}
+
+impl Default for OverlayState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            frozen: false,
+        }
+    }
+}
+
+impl OverlayState {
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub fn unfreeze(&mut self) {
+        self.frozen = false;
+    }
+
+    pub fn toggle_freeze(&mut self) {
+        self.frozen = !self.frozen;
+    }
+}
+
+/// How the overlay is added to the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlayMode {
+    /// Spawns a dedicated camera rendering the overlay over its target
+    #[default]
+    Camera,
+    /// Draws the overlay at the end of the 2d and 3d render graphs of the cameras
+    /// tagged with [`CameraOverlay`], without spawning an extra camera
+    RenderGraph,
+}
+
+pub struct OverlayPlugin {
+    /// Where the overlay camera renders the overlay.
+    ///
+    /// Use [`RenderTarget::Window`] to attach the overlay to a specific window, or
+    /// [`RenderTarget::Image`] with an image created by [`overlay_image`] to show the overlay
+    /// in a ui node, on a 3d surface or in an editor panel.
+    ///
+    /// Only used by the camera spawned with [`OverlayMode::Camera`] and
+    /// [`OverlayPlugin::spawn_camera`], a warning is logged when it's set otherwise.
+    ///
+    /// Defaults to the primary window
+    pub target: RenderTarget,
+    /// Defaults to [`OverlayMode::Camera`]
+    pub mode: OverlayMode,
+    /// Spawns the overlay camera when using [`OverlayMode::Camera`].
+    /// Disable it to spawn and order a [`CameraOverlayBundle`] yourself.
+    ///
+    /// The plugin despawns the camera it spawned when the overlay is disabled. The cameras you
+    /// spawn are kept, they don't draw the overlay while it's disabled and clear their image
+    /// target, deactivate or despawn them to skip them entirely.
+    ///
+    /// Defaults to true
+    pub spawn_camera: bool,
+    /// The priority of the spawned overlay camera, a warning is logged when no camera is spawned.
+    ///
+    /// Defaults to None, which renders over every other camera of a window
+    /// and before the cameras using an image target
+    pub camera_priority: Option<isize>,
+    /// The directory where [`OverlayState::enabled`], [`OverlayConfig::compact`] and
+    /// [`OverlayConfig::ui_scale`] are saved when they change at runtime, in `overlay_state.ron`.
+    /// They are restored from it at startup and take precedence over the [`OverlayConfig::settings`]
+    /// when they are first loaded, later modifications of the settings file override them.
+    ///
+    /// [`OverlayState::frozen`] isn't saved since the frozen frames are lost on restart.
+    /// The [`OverlayMode`] is chosen when building the plugin and the overlay is always drawn in the
+    /// top left corner of its target, so neither can change at runtime.
+    ///
+    /// Defaults to None, which doesn't persist anything
+    pub persistence_dir: Option<PathBuf>,
+}
+
+impl Default for OverlayPlugin {
+    fn default() -> Self {
+        Self {
+            target: default(),
+            mode: default(),
+            spawn_camera: true,
+            camera_priority: None,
+            persistence_dir: None,
         }
     }
 }
 
-#[derive(Default)]
-pub struct OverlayPlugin;
+/// Marks the camera spawned by the [`OverlayPlugin`]
+#[derive(Component)]
+struct SpawnedOverlayCamera;
+
+/// How the [`OverlayPlugin`] spawns the overlay camera
+#[derive(Resource)]
+struct OverlayCameraSettings {
+    target: RenderTarget,
+    priority: Option<isize>,
+}
+
 impl Plugin for OverlayPlugin {
     fn build(&self, app: &mut App) {
         if app
@@ -91,10 +545,83 @@ impl Plugin for OverlayPlugin {
         }
 
         app.add_plugin(ExtractComponentPlugin::<CameraOverlay>::default())
-            .add_startup_system(move |mut commands: Commands| {
-                commands.spawn(CameraOverlayBundle::default());
+            .init_resource::<OverlayConfig>()
+            .init_resource::<HitchContext>()
+            .init_resource::<FramePacing>()
+            .init_resource::<OverlayState>()
+            .init_resource::<FrameHistory>()
+            .init_resource::<HoveredFrame>()
+            .init_resource::<GlyphAdvances>()
+            .init_resource::<CustomRows>()
+            .add_event::<FrameHitch>()
+            .register_type::<OverlayConfig>()
+            .register_type::<OverlayState>()
+            .register_type::<FrameHistory>()
+            .register_type::<FrameSample>()
+            .register_type::<FramePacing>()
+            .register_type::<HoveredFrame>()
+            .register_type::<CameraOverlay>()
+            .register_type::<BudgetLine>()
+            .register_type::<GraphScale>()
+            .register_type::<BarWidth>()
+            .register_type::<GraphStyle>()
+            .register_type::<ColorPalette>()
+            .register_type::<OverlayFont>()
+            .register_type::<TextRendering>()
+            .register_type::<HitchLogConfig>()
+            .register_type::<OverlayRow>()
+            .register_type::<FrameStat>()
+            .register_type::<HistogramConfig>()
+            .register_type::<BinSpacing>()
+            .register_type::<PacingConfig>()
+            .register_type::<OverlayTheme>()
+            .register_type::<NumberFormat>()
+            .register_type::<NumberUnits>()
+            .add_asset::<OverlaySettings>()
+            .init_asset_loader::<OverlaySettingsLoader>()
+            .add_startup_system(font::load_font)
+            .add_system_to_stage(CoreStage::PreUpdate, settings::apply_settings)
+            .add_system(toggle_freeze)
+            .add_system(toggle_compact)
+            .add_system_to_stage(CoreStage::PostUpdate, font::update_font)
+            .add_system_to_stage(CoreStage::PostUpdate, history::record_frame)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                hover::update_hovered_frame.after(history::record_frame),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                pacing::update_frame_pacing.after(history::record_frame),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                hitch::detect_hitches.after(history::record_frame),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                hitch::log_hitches.after(hitch::detect_hitches),
+            );
+
+        let spawns_camera = self.mode == OverlayMode::Camera && self.spawn_camera;
+        if !spawns_camera && self.target != RenderTarget::default() {
+            warn!("OverlayPlugin::target is ignored since the plugin doesn't spawn the overlay camera");
+        }
+        if !spawns_camera && self.camera_priority.is_some() {
+            warn!("OverlayPlugin::camera_priority is ignored since the plugin doesn't spawn the overlay camera");
+        }
+        if spawns_camera {
+            app.insert_resource(OverlayCameraSettings {
+                target: self.target.clone(),
+                priority: self.camera_priority,
             })
-            .add_startup_system(load_font);
+            .add_system(spawn_overlay_camera);
+        }
+
+        if let Some(dir) = &self.persistence_dir {
+            app.insert_resource(OverlayPersistence::new(dir.clone()))
+                .add_startup_system(persistence::restore_state)
+                .add_system_to_stage(CoreStage::Last, persistence::save_state);
+        }
 
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
@@ -105,14 +632,37 @@ impl Plugin for OverlayPlugin {
             .init_resource::<OverlayConfig>()
             .init_resource::<OverlayDataUniform>()
             .init_resource::<Frametimes>()
+            .init_resource::<GlyphAdvances>()
+            .init_resource::<OverlayViewBuffers>()
+            .init_resource::<WindowScaleFactors>()
+            .init_resource::<CustomRows>()
+            .init_resource::<GraphSeriesValues>()
+            .init_resource::<Histogram>()
             .init_resource::<OverlayBindGroups>()
             .init_resource::<OverlayPipeline>()
+            .init_resource::<OverlayState>()
+            .add_system_to_stage(RenderStage::Extract, extract_overlay_config)
+            .add_system_to_stage(RenderStage::Extract, extract_overlay_state)
+            .add_system_to_stage(RenderStage::Extract, extract_custom_rows)
             .add_system_to_stage(RenderStage::Extract, extract_overlay_camera)
             .add_system_to_stage(RenderStage::Extract, update_frametimes)
+            .add_system_to_stage(RenderStage::Extract, view::extract_window_scale_factors)
             .add_system_to_stage(RenderStage::Extract, extract_font_handle)
-            .add_system_to_stage(RenderStage::Prepare, prepare_overlay_bind_group);
+            .add_system_to_stage(RenderStage::Prepare, prepare_graph_range)
+            .add_system_to_stage(RenderStage::Prepare, histogram::prepare_histogram)
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                prepare_overlay_bind_group
+                    .after(prepare_graph_range)
+                    .after(histogram::prepare_histogram),
+            )
+            .add_system_to_stage(
+                RenderStage::Prepare,
+                view::prepare_overlay_views.after(prepare_overlay_bind_group),
+            );
 
-        let pass_node_overlay = OverlayNode::new(&mut render_app.world);
+        let render_pipeline_id = OverlayNode::queue_pipeline(&mut render_app.world);
+        let pass_node_overlay = OverlayNode::new(&mut render_app.world, render_pipeline_id);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
 
         let mut overlay_graph = RenderGraph::default();
@@ -128,16 +678,120 @@ impl Plugin for OverlayPlugin {
             )
             .unwrap();
         graph.add_sub_graph(graph::NAME, overlay_graph);
+
+        if self.mode == OverlayMode::RenderGraph {
+            let core_graphs = [
+                (
+                    core_2d::graph::NAME,
+                    core_2d::graph::input::VIEW_ENTITY,
+                    core_2d::graph::node::MAIN_PASS,
+                ),
+                (
+                    core_3d::graph::NAME,
+                    core_3d::graph::input::VIEW_ENTITY,
+                    core_3d::graph::node::MAIN_PASS,
+                ),
+            ];
+            for (graph_name, view_entity, main_pass) in core_graphs {
+                let pass_node_overlay = OverlayNode::new(&mut render_app.world, render_pipeline_id);
+                let mut graph = render_app.world.resource_mut::<RenderGraph>();
+                let core_graph = match graph.get_sub_graph_mut(graph_name) {
+                    Some(core_graph) => core_graph,
+                    None => continue,
+                };
+
+                core_graph.add_node(graph::NODE, pass_node_overlay);
+                let input_node_id = core_graph.input_node().unwrap().id;
+                core_graph
+                    .add_slot_edge(input_node_id, view_entity, graph::NODE, graph::IN_VIEW)
+                    .unwrap();
+                // The main pass is the last node of the core graphs, the ui is drawn after it
+                core_graph.add_node_edge(main_pass, graph::NODE).unwrap();
+                if core_graph
+                    .get_node_state(draw_ui_graph::node::UI_PASS)
+                    .is_ok()
+                {
+                    core_graph
+                        .add_node_edge(draw_ui_graph::node::UI_PASS, graph::NODE)
+                        .unwrap();
+                }
+            }
+        }
     }
 }
 
-fn load_font(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // TODO embed font in plugin
-    commands.insert_resource(FontImage(asset_server.load("font.png")));
+/// Creates an image the overlay can be rendered to with [`OverlayPlugin::target`]
+///
+/// The overlay is drawn from the top left corner of the image and the rest of the image is transparent.
+pub fn overlay_image(size: UVec2) -> Image {
+    let size = Extent3d {
+        width: size.x,
+        height: size.y,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("overlay_image"),
+            size,
+            dimension: TextureDimension::D2,
+            // Needs to match the format of the overlay pipeline
+            format: TextureFormat::bevy_default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..default()
+    };
+    // fill the image with zeros
+    image.resize(size);
+    image
 }
 
-#[derive(Resource, Clone)]
-pub struct FontImage(Handle<Image>);
+/// Spawns the overlay camera while the overlay is enabled and despawns it when it's disabled
+fn spawn_overlay_camera(
+    mut commands: Commands,
+    settings: Res<OverlayCameraSettings>,
+    state: Res<OverlayState>,
+    cameras: Query<Entity, With<SpawnedOverlayCamera>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    if state.enabled && cameras.is_empty() {
+        let mut bundle = CameraOverlayBundle::with_target(settings.target.clone());
+        if let Some(priority) = settings.priority {
+            bundle.camera.priority = priority;
+        }
+        commands.spawn((bundle, SpawnedOverlayCamera));
+    } else if !state.enabled {
+        for entity in &cameras {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn toggle_freeze(
+    keys: Option<Res<Input<KeyCode>>>,
+    config: Res<OverlayConfig>,
+    mut state: ResMut<OverlayState>,
+) {
+    if let (Some(keys), Some(freeze_key)) = (keys, config.freeze_key) {
+        if keys.just_pressed(freeze_key) {
+            state.toggle_freeze();
+        }
+    }
+}
+
+fn toggle_compact(keys: Option<Res<Input<KeyCode>>>, mut config: ResMut<OverlayConfig>) {
+    if let (Some(keys), Some(compact_key)) = (keys, config.compact_key) {
+        if keys.just_pressed(compact_key) {
+            config.compact = !config.compact;
+        }
+    }
+}
 
 #[derive(Debug, Clone, ShaderType, Default)]
 pub struct OverlayConfigUniform {
@@ -149,19 +803,105 @@ pub struct OverlayConfigUniform {
     len: i32,
     colors: Mat4,
     dts: Vec4,
+    budget_line_count: u32,
+    budget_line_dts: Vec4,
+    budget_line_colors: Mat4,
+    budget_line_labels: UVec4,
+    scale_mode: u32,
+    bar_count: u32,
+    graph_style: u32,
+    antialiasing: u32,
+    series_count: u32,
+    series_colors: Mat4,
+    pacing_tolerance: f32,
+    row_count: u32,
+    // The kind of each row, packed by 4
+    rows: [UVec4; MAX_OVERLAY_ROWS / 4],
+    background: Vec4,
+    text_color: Vec4,
+    // The alpha is 0 when the shadow is disabled
+    shadow_color: Vec4,
+    padding: f32,
+    corner_radius: f32,
+    // 0 when the hatching is disabled
+    hatch_threshold: f32,
+    text_sdf: u32,
+    // The alpha is 0 when the outline is disabled
+    outline_color: Vec4,
 }
 
 impl OverlayConfigUniform {
-    fn new(dts: Vec4, buffer_len: usize, colors: Mat4) -> Self {
+    fn new(config: &OverlayConfig) -> Self {
+        let dts = config.dts;
+
+        let mut budget_line_dts = Vec4::ZERO;
+        let mut budget_line_colors = [[0.0; 4]; MAX_BUDGET_LINES];
+        let mut budget_line_labels = UVec4::ZERO;
+        let mut series_colors = [[0.0; 4]; MAX_GRAPH_SERIES];
+        let series = &config.series[..config.series.len().min(MAX_GRAPH_SERIES)];
+        for (i, series) in series.iter().enumerate() {
+            series_colors[i] = series.color.as_linear_rgba_f32();
+        }
+
+        let budget_lines = &config.budget_lines[..config.budget_lines.len().min(MAX_BUDGET_LINES)];
+        for (i, line) in budget_lines.iter().enumerate() {
+            budget_line_dts[i] = line.dt;
+            budget_line_colors[i] = line.color.as_linear_rgba_f32();
+            budget_line_labels[i] = line.label as u32;
+        }
+
+        let mut row_kinds = [UVec4::ZERO; MAX_OVERLAY_ROWS / 4];
+        let row_count = if config.compact {
+            row_kinds[0][0] = layout::COMPACT_ROW_KIND;
+            1
+        } else {
+            let rows = config.shown_rows();
+            for (i, row) in rows.iter().enumerate() {
+                row_kinds[i / 4][i % 4] = row.kind();
+            }
+            rows.len()
+        };
+
         Self {
             dt_min: dts[0],
             dt_max: dts[3],
             dt_min_log2: dts[0].log2(),
             dt_max_log2: dts[3].log2(),
-            max_width: buffer_len as f32,
-            len: buffer_len as i32,
-            colors,
+            max_width: config.graph_len() as f32,
+            len: config.graph_len() as i32,
+            colors: Mat4::from_cols_array_2d(
+                &config.colors.map(|color| color.as_linear_rgba_f32()),
+            ),
             dts,
+            budget_line_count: budget_lines.len() as u32,
+            budget_line_dts,
+            budget_line_colors: Mat4::from_cols_array_2d(&budget_line_colors),
+            budget_line_labels,
+            scale_mode: config.scale.as_u32(),
+            bar_count: config.bar_count().unwrap_or_default() as u32,
+            graph_style: config.graph_style.as_u32(),
+            antialiasing: config.antialiasing as u32,
+            series_count: series.len() as u32,
+            series_colors: Mat4::from_cols_array_2d(&series_colors),
+            pacing_tolerance: config.pacing.tolerance,
+            row_count: row_count as u32,
+            rows: row_kinds,
+            background: config.theme.background.as_linear_rgba_f32().into(),
+            text_color: config.theme.text_color.as_linear_rgba_f32().into(),
+            shadow_color: config
+                .theme
+                .text_shadow
+                .map_or([0.0; 4], |color| color.as_linear_rgba_f32())
+                .into(),
+            padding: config.theme.padding,
+            corner_radius: config.theme.corner_radius,
+            hatch_threshold: config.hatch_threshold.unwrap_or(0.0),
+            text_sdf: (config.text_rendering == TextRendering::Sdf) as u32,
+            outline_color: config
+                .theme
+                .text_outline
+                .map_or([0.0; 4], |color| color.as_linear_rgba_f32())
+                .into(),
         }
     }
 }
@@ -170,8 +910,20 @@ impl OverlayConfigUniform {
 pub struct OverlayDataUniform {
     pub fps: f32,
     pub frame_count: u32,
-    pub resolution: UVec2,
-    pub scale: f32,
+    /// The range of delta times covered by the graph when using a linear [`GraphScale`]
+    pub graph_min: f32,
+    pub graph_max: f32,
+    /// See [`FramePacing`]
+    pub pacing_target_interval: f32,
+    pub pacing_average_delta: f32,
+    pub pacing_deviating_frames: u32,
+    /// The index of the [`HoveredFrame`] in the frametimes buffer or -1.
+    /// Only the views drawn on the primary window show it.
+    pub hovered_index: i32,
+    pub hovered_frametime: f32,
+    pub hovered_frame: u32,
+    /// In seconds
+    pub hovered_timestamp: f32,
 }
 
 impl Default for OverlayDataUniform {
@@ -179,12 +931,21 @@ impl Default for OverlayDataUniform {
         Self {
             fps: 0.0,
             frame_count: 0,
-            resolution: UVec2::ZERO,
-            scale: 1.0,
+            graph_min: 0.0,
+            graph_max: 0.0,
+            pacing_target_interval: 0.0,
+            pacing_average_delta: 0.0,
+            pacing_deviating_frames: 0,
+            hovered_index: -1,
+            hovered_frametime: 0.0,
+            hovered_frame: 0,
+            hovered_timestamp: 0.0,
         }
     }
 }
 
+/// The frametimes displayed in the graph, only available in the render world.
+/// Use the [`FrameHistory`] to read the frametimes from the main world.
 #[derive(Debug, Clone, ShaderType, Resource)]
 pub struct Frametimes {
     pub values: [f32; FRAMETIME_BUFFER_LEN],
@@ -203,6 +964,33 @@ impl Frametimes {
         self.values.rotate_left(1);
         self.values[FRAMETIME_BUFFER_LEN - 1] = value;
     }
+
+    /// The frametimes from the `start` of the graph, without the empty start of the buffer when not
+    /// enough frames have been recorded yet
+    pub(crate) fn recorded(&self, start: usize) -> impl Iterator<Item = f32> + '_ {
+        self.values[start..].iter().copied().filter(|dt| *dt > 0.0)
+    }
+}
+
+/// The recent values of each [`GraphSeries`], in seconds
+#[derive(Debug, Clone, ShaderType, Resource)]
+pub struct GraphSeriesValues {
+    pub values: [[f32; FRAMETIME_BUFFER_LEN]; MAX_GRAPH_SERIES],
+}
+
+impl Default for GraphSeriesValues {
+    fn default() -> Self {
+        Self {
+            values: [[0.0; FRAMETIME_BUFFER_LEN]; MAX_GRAPH_SERIES],
+        }
+    }
+}
+
+impl GraphSeriesValues {
+    pub fn push(&mut self, series: usize, value: f32) {
+        self.values[series].rotate_left(1);
+        self.values[series][FRAMETIME_BUFFER_LEN - 1] = value;
+    }
 }
 
 #[derive(Resource)]
@@ -210,6 +998,9 @@ pub struct OverlayBindGroups {
     pub config_buffer: UniformBuffer<OverlayConfigUniform>,
     pub data_buffer: UniformBuffer<OverlayDataUniform>,
     pub frametimes_buffer: StorageBuffer<Frametimes>,
+    pub series_buffer: StorageBuffer<GraphSeriesValues>,
+    pub histogram_buffer: StorageBuffer<Histogram>,
+    pub glyph_advances_buffer: StorageBuffer<GlyphAdvances>,
     pub font_image_texture: OwnedBindingResource,
     pub font_image_sampler: OwnedBindingResource,
 }
@@ -221,14 +1012,13 @@ impl FromWorld for OverlayBindGroups {
         let config = world.resource::<OverlayConfig>();
         let data = world.resource::<OverlayDataUniform>();
         let frametimes = world.resource::<Frametimes>();
+        let series_values = world.resource::<GraphSeriesValues>();
+        let histogram = world.resource::<Histogram>();
+        let glyph_advances = world.resource::<GlyphAdvances>();
         let fallback_image = world.resource::<FallbackImage>();
 
         let mut config_buffer = UniformBuffer::default();
-        config_buffer.set(OverlayConfigUniform::new(
-            config.dts,
-            config.buffer_len,
-            config.colors,
-        ));
+        config_buffer.set(OverlayConfigUniform::new(config));
         config_buffer.write_buffer(render_device, render_queue);
 
         let mut data_buffer = UniformBuffer::default();
@@ -239,6 +1029,18 @@ impl FromWorld for OverlayBindGroups {
         frametimes_buffer.set(frametimes.clone());
         frametimes_buffer.write_buffer(render_device, render_queue);
 
+        let mut series_buffer = StorageBuffer::default();
+        series_buffer.set(series_values.clone());
+        series_buffer.write_buffer(render_device, render_queue);
+
+        let mut histogram_buffer = StorageBuffer::default();
+        histogram_buffer.set(histogram.clone());
+        histogram_buffer.write_buffer(render_device, render_queue);
+
+        let mut glyph_advances_buffer = StorageBuffer::default();
+        glyph_advances_buffer.set(glyph_advances.clone());
+        glyph_advances_buffer.write_buffer(render_device, render_queue);
+
         let font_image_texture =
             OwnedBindingResource::TextureView(fallback_image.texture_view.clone());
         let font_image_sampler = OwnedBindingResource::Sampler(fallback_image.sampler.clone());
@@ -247,6 +1049,9 @@ impl FromWorld for OverlayBindGroups {
             config_buffer,
             data_buffer,
             frametimes_buffer,
+            series_buffer,
+            histogram_buffer,
+            glyph_advances_buffer,
             font_image_texture,
             font_image_sampler,
         }
@@ -260,6 +1065,24 @@ impl OverlayBindGroups {
     }
 }
 
+fn extract_overlay_config(mut commands: Commands, config: Extract<Res<OverlayConfig>>) {
+    if config.is_changed() {
+        commands.insert_resource(config.clone());
+    }
+}
+
+fn extract_custom_rows(mut commands: Commands, custom_rows: Extract<Res<CustomRows>>) {
+    if custom_rows.is_changed() {
+        commands.insert_resource(custom_rows.clone());
+    }
+}
+
+fn extract_overlay_state(mut commands: Commands, state: Extract<Res<OverlayState>>) {
+    if state.is_changed() {
+        commands.insert_resource(state.clone());
+    }
+}
+
 fn extract_overlay_camera(
     mut commands: Commands,
     cameras_overlay: Extract<Query<(Entity, &Camera), With<CameraOverlay>>>,
@@ -273,15 +1096,27 @@ fn extract_overlay_camera(
 
 fn update_frametimes(
     diagnostics: Extract<Res<Diagnostics>>,
+    history: Extract<Res<FrameHistory>>,
+    hovered: Extract<Res<HoveredFrame>>,
     mut frametimes: ResMut<Frametimes>,
+    mut series_values: ResMut<GraphSeriesValues>,
     mut overlay_data: ResMut<OverlayDataUniform>,
     frame_count: Extract<Res<FrameCount>>,
-    windows: Extract<Res<Windows>>,
+    pacing: Extract<Res<FramePacing>>,
 ) {
-    if let Some(frame_time_diagnostic) = diagnostics.get(FrameTimeDiagnosticsPlugin::FRAME_TIME) {
-        if let Some(dt) = frame_time_diagnostic.value() {
-            frametimes.push(dt as f32 / 1000.0);
+    if history.is_changed() {
+        *frametimes = history.displayed_frametimes();
+        *series_values = history.displayed_series_values();
+    }
+
+    match hovered.sample {
+        Some(sample) => {
+            overlay_data.hovered_index = hovered.buffer_index as i32;
+            overlay_data.hovered_frametime = sample.frametime;
+            overlay_data.hovered_frame = sample.frame;
+            overlay_data.hovered_timestamp = sample.timestamp.as_secs_f32();
         }
+        None => overlay_data.hovered_index = -1,
     }
 
     if let Some(fps_diagnostic) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
@@ -291,28 +1126,106 @@ fn update_frametimes(
     }
 
     overlay_data.frame_count = frame_count.0;
-    if let Some(window) = windows.get_primary() {
-        overlay_data.resolution.x = window.physical_width();
-        overlay_data.resolution.y = window.physical_height();
-        overlay_data.scale = window.scale_factor() as f32;
-    }
+    overlay_data.pacing_target_interval = pacing.target_interval;
+    overlay_data.pacing_average_delta = pacing.average_delta;
+    overlay_data.pacing_deviating_frames = pacing.deviating_frames;
 }
 
-fn extract_font_handle(mut commands: Commands, font_image: Extract<Res<FontImage>>) {
+fn extract_font_handle(
+    mut commands: Commands,
+    font_image: Extract<Res<FontImage>>,
+    glyph_advances: Extract<Res<GlyphAdvances>>,
+) {
     commands.insert_resource(font_image.clone());
+    if glyph_advances.is_changed() {
+        commands.insert_resource(glyph_advances.clone());
+    }
+}
+
+/// Margin added around the recent frametimes when using [`GraphScale::Auto`], relative to their range
+const AUTO_RANGE_MARGIN: f32 = 0.1;
+/// The auto range only shrinks once the recent frametimes use less than this fraction of it
+const AUTO_RANGE_SHRINK_THRESHOLD: f32 = 0.75;
+/// How much of the difference with the recent frametimes is removed each frame while shrinking
+const AUTO_RANGE_SHRINK_SPEED: f32 = 0.05;
+
+fn prepare_graph_range(
+    config: Res<OverlayConfig>,
+    frametimes: Res<Frametimes>,
+    mut overlay_data: ResMut<OverlayDataUniform>,
+    mut auto_range: Local<Option<Vec2>>,
+) {
+    let range = match config.scale {
+        GraphScale::Log2 => Vec2::new(config.dts[0], config.dts[3]),
+        // The bounds can come from a settings file
+        GraphScale::Linear { min, max } => Vec2::new(min.min(max), min.max(max)),
+        GraphScale::Auto => {
+            let (min, max) = frametimes
+                .recorded(config.graph_start())
+                .fold((f32::MAX, f32::MIN), |(min, max), dt| {
+                    (min.min(dt), max.max(dt))
+                });
+            if min > max {
+                // No frametimes yet
+                return;
+            }
+
+            // Keep at least 1ms of range so a perfectly stable frametime is still visible
+            let margin = (max - min).max(0.001) * AUTO_RANGE_MARGIN;
+            let target = Vec2::new((min - margin).max(0.0), max + margin);
+            let range = match *auto_range {
+                Some(range) => {
+                    let shrink_threshold = (range.y - range.x) * AUTO_RANGE_SHRINK_THRESHOLD;
+                    let should_shrink = target.y - target.x < shrink_threshold;
+                    let grow_or_shrink = |current: f32, target: f32, grows: bool| {
+                        if grows {
+                            target
+                        } else if should_shrink {
+                            current + (target - current) * AUTO_RANGE_SHRINK_SPEED
+                        } else {
+                            current
+                        }
+                    };
+                    Vec2::new(
+                        grow_or_shrink(range.x, target.x, target.x < range.x),
+                        grow_or_shrink(range.y, target.y, target.y > range.y),
+                    )
+                }
+                None => target,
+            };
+            *auto_range = Some(range);
+            range
+        }
+    };
+
+    overlay_data.graph_min = range.x;
+    overlay_data.graph_max = range.y;
 }
 
 fn prepare_overlay_bind_group(
     mut bind_group: ResMut<OverlayBindGroups>,
     mut pipeline: ResMut<OverlayPipeline>,
+    config: Res<OverlayConfig>,
     data_uniform: Res<OverlayDataUniform>,
     frametimes: Res<Frametimes>,
+    series_values: Res<GraphSeriesValues>,
+    histogram: Res<Histogram>,
+    glyph_advances: Res<GlyphAdvances>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     font_handle: Res<FontImage>,
     images: Res<RenderAssets<Image>>,
-    mut font_loaded: Local<bool>,
+    mut bound_font: Local<Option<Handle<Image>>>,
 ) {
+    if config.is_changed() {
+        bind_group
+            .config_buffer
+            .set(OverlayConfigUniform::new(&config));
+        bind_group
+            .config_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+
     if frametimes.is_changed() {
         bind_group.frametimes_buffer.set(frametimes.clone());
         bind_group
@@ -320,6 +1233,27 @@ fn prepare_overlay_bind_group(
             .write_buffer(&render_device, &render_queue);
     }
 
+    if series_values.is_changed() {
+        bind_group.series_buffer.set(series_values.clone());
+        bind_group
+            .series_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+
+    if histogram.is_changed() {
+        bind_group.histogram_buffer.set(histogram.clone());
+        bind_group
+            .histogram_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+
+    if glyph_advances.is_changed() {
+        bind_group.glyph_advances_buffer.set(glyph_advances.clone());
+        bind_group
+            .glyph_advances_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+
     if data_uniform.is_changed() {
         bind_group.data_buffer.set(data_uniform.clone());
         bind_group
@@ -327,16 +1261,18 @@ fn prepare_overlay_bind_group(
             .write_buffer(&render_device, &render_queue)
     }
 
-    if !*font_loaded {
+    // The font can change at runtime, the previous one stays bound until the new one is loaded
+    if bound_font.as_ref() != Some(&font_handle.0) {
         if let Some(image) = images.get(&font_handle.0) {
             bind_group.update_font_image(image);
             pipeline.update_bind_group(&render_device, &bind_group);
-            *font_loaded = true;
+            *bound_font = Some(font_handle.0.clone_weak());
         }
     }
 }
 
-#[derive(Component, Clone, Copy, Default)]
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
 pub struct CameraOverlay;
 impl ExtractComponent for CameraOverlay {
     type Query = &'static Self;
@@ -372,3 +1308,15 @@ impl Default for CameraOverlayBundle {
         }
     }
 }
+
+impl CameraOverlayBundle {
+    pub fn with_target(target: RenderTarget) -> Self {
+        let mut bundle = Self::default();
+        if matches!(target, RenderTarget::Image(_)) {
+            // Render the image before the cameras using it
+            bundle.camera.priority = -1;
+        }
+        bundle.camera.target = target;
+        bundle
+    }
+}
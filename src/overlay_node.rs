@@ -1,6 +1,7 @@
 use bevy::{
     prelude::*,
     render::{
+        camera::{ExtractedCamera, RenderTarget},
         render_graph::{Node, NodeRunError, RenderGraphContext, SlotInfo, SlotType},
         render_phase::TrackedRenderPass,
         render_resource::{
@@ -11,7 +12,11 @@ use bevy::{
     },
 };
 
-use crate::{pipeline::OverlayPipeline, CameraOverlay};
+use crate::{
+    pipeline::OverlayPipeline,
+    view::{OverlayViewBuffers, OverlayViewOffsets},
+    CameraOverlay, OverlayState,
+};
 
 pub(crate) mod graph {
     pub const NAME: &str = "OVERLAY";
@@ -20,20 +25,30 @@ pub(crate) mod graph {
     pub const IN_VIEW: &str = "OVERLAY_IN_VIEW";
 }
 pub(crate) struct OverlayNode {
-    query: QueryState<&'static ViewTarget, With<CameraOverlay>>,
+    query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static ExtractedCamera,
+            Option<&'static OverlayViewOffsets>,
+        ),
+        With<CameraOverlay>,
+    >,
     render_pipeline_id: CachedRenderPipelineId,
 }
 impl OverlayNode {
-    pub(crate) fn new(world: &mut World) -> Self {
+    /// Queues the overlay pipeline, the returned id can be shared by every overlay node
+    pub(crate) fn queue_pipeline(world: &mut World) -> CachedRenderPipelineId {
         let overlay_pipeline = (*world.resource::<OverlayPipeline>()).clone();
-        let render_pipeline = world
-            .resource_mut::<PipelineCache>()
-            .queue_render_pipeline(overlay_pipeline.descriptor());
         // world.resource_mut::<PipelineCache>().process_queue();
+        world
+            .resource_mut::<PipelineCache>()
+            .queue_render_pipeline(overlay_pipeline.descriptor())
+    }
 
+    pub(crate) fn new(world: &mut World, render_pipeline_id: CachedRenderPipelineId) -> Self {
         Self {
             query: world.query_filtered(),
-            render_pipeline_id: render_pipeline,
+            render_pipeline_id,
         }
     }
 }
@@ -55,11 +70,31 @@ impl Node for OverlayNode {
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.get_input_entity(graph::IN_VIEW)?;
 
-        let target = if let Ok(result) = self.query.get_manual(world, view_entity) {
-            result
-        } else {
-            return Ok(());
+        let (target, camera, view_offsets) =
+            if let Ok(result) = self.query.get_manual(world, view_entity) {
+                result
+            } else {
+                return Ok(());
+            };
+
+        // The views are only prepared while the overlay is enabled
+        let view = view_offsets
+            .zip(world.resource::<OverlayViewBuffers>().bind_group.as_ref())
+            .filter(|_| world.resource::<OverlayState>().enabled);
+
+        // The overlay is blended over the target, but nothing else clears an image target
+        // rendered by an overlay camera
+        let load = match camera.target {
+            RenderTarget::Image(_) if camera.render_graph == graph::NAME => {
+                LoadOp::Clear(Color::NONE.into())
+            }
+            _ => LoadOp::Load,
         };
+        // A disabled overlay still clears the image of an overlay camera that wasn't despawned,
+        // so it doesn't keep showing the last frame of the overlay
+        if view.is_none() && matches!(load, LoadOp::Load) {
+            return Ok(());
+        }
 
         let target = ViewTarget {
             view: target.view.clone(),
@@ -67,10 +102,9 @@ impl Node for OverlayNode {
         };
         let pass_descriptor = RenderPassDescriptor {
             label: Some("overlay"),
-            color_attachments: &[Some(target.get_color_attachment(Operations {
-                load: LoadOp::Load,
-                store: true,
-            }))],
+            color_attachments: &[Some(
+                target.get_color_attachment(Operations { load, store: true }),
+            )],
             depth_stencil_attachment: None,
         };
 
@@ -82,12 +116,19 @@ impl Node for OverlayNode {
 
         let pipeline = world.resource::<OverlayPipeline>();
 
-        if let Some(render_pipeline) = world
+        let render_pipeline = world
             .resource::<PipelineCache>()
-            .get_render_pipeline(self.render_pipeline_id)
+            .get_render_pipeline(self.render_pipeline_id);
+        if let (Some(render_pipeline), Some((view_offsets, view_bind_group))) =
+            (render_pipeline, view)
         {
             tracked.set_render_pipeline(render_pipeline);
             tracked.set_bind_group(0, &pipeline.bind_group, &[]);
+            tracked.set_bind_group(
+                1,
+                view_bind_group,
+                &[view_offsets.uniform, view_offsets.text],
+            );
 
             tracked.draw(0..3, 0..1);
         }
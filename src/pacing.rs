@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use crate::{FrameHistory, OverlayConfig};
+
+/// Configures the frame pacing metrics
+///
+/// Use [`crate::OverlayRow::Pacing`] and [`crate::OverlayRow::PacingGraph`] to show them in the overlay.
+#[derive(Debug, Clone, Reflect, FromReflect)]
+pub struct PacingConfig {
+    /// The expected interval between two frames in seconds, like 1/60 for a 60hz display.
+    /// The average frametime is used when it's not set or not positive.
+    ///
+    /// Defaults to None
+    pub target_interval: Option<f32>,
+    /// A frame is deviating when its frametime differs from the target interval by more than this
+    /// fraction of the target interval
+    ///
+    /// Defaults to 0.1
+    pub tolerance: f32,
+}
+
+impl Default for PacingConfig {
+    fn default() -> Self {
+        Self {
+            target_interval: None,
+            tolerance: 0.1,
+        }
+    }
+}
+
+/// Frame pacing metrics of the frames of the [`FrameHistory`]
+///
+/// The average fps can look fine while the frames are delivered unevenly, which is perceived as stutter.
+/// All the durations are in seconds.
+#[derive(Debug, Default, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct FramePacing {
+    /// The interval the frames are compared to
+    pub target_interval: f32,
+    /// The average absolute difference between the frametimes of two consecutive frames
+    pub average_delta: f32,
+    /// The biggest difference between the frametimes of two consecutive frames
+    pub max_delta: f32,
+    /// The amount of frames deviating from the target interval by more than [`PacingConfig::tolerance`]
+    pub deviating_frames: u32,
+    /// The variance of the interval between frames, in seconds squared
+    pub variance: f32,
+}
+
+impl FramePacing {
+    fn new(config: &PacingConfig, frametimes: &[f32]) -> Self {
+        if frametimes.is_empty() {
+            return Self::default();
+        }
+
+        let len = frametimes.len() as f32;
+        let mean = frametimes.iter().sum::<f32>() / len;
+        let variance = frametimes.iter().map(|dt| (dt - mean).powi(2)).sum::<f32>() / len;
+        let target_interval = config
+            .target_interval
+            .filter(|interval| *interval > 0.0)
+            .unwrap_or(mean);
+
+        let deltas = frametimes
+            .iter()
+            .zip(frametimes.iter().skip(1))
+            .map(|(previous, dt)| (dt - previous).abs());
+        let (delta_sum, max_delta) = deltas.fold((0.0, 0.0_f32), |(sum, max), delta| {
+            (sum + delta, max.max(delta))
+        });
+
+        let max_deviation = target_interval * config.tolerance;
+        let deviating_frames = frametimes
+            .iter()
+            .filter(|dt| (*dt - target_interval).abs() > max_deviation)
+            .count();
+
+        Self {
+            target_interval,
+            average_delta: delta_sum / (len - 1.0).max(1.0),
+            max_delta,
+            deviating_frames: deviating_frames as u32,
+            variance,
+        }
+    }
+}
+
+pub(crate) fn update_frame_pacing(
+    history: Res<FrameHistory>,
+    config: Res<OverlayConfig>,
+    mut pacing: ResMut<FramePacing>,
+) {
+    if history.is_changed() || config.is_changed() {
+        let frametimes: Vec<f32> = history.frametimes().collect();
+        *pacing = FramePacing::new(&config.pacing, &frametimes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FrameSample;
+
+    fn assert_close(value: f32, expected: f32) {
+        assert!((value - expected).abs() < 1e-6, "{value} != {expected}");
+    }
+
+    #[test]
+    fn metrics() {
+        let config = PacingConfig {
+            target_interval: Some(0.016),
+            tolerance: 0.1,
+        };
+        let pacing = FramePacing::new(&config, &[0.016, 0.016, 0.032, 0.016]);
+        assert_eq!(pacing.target_interval, 0.016);
+        assert_close(pacing.average_delta, 0.032 / 3.0);
+        assert_close(pacing.max_delta, 0.016);
+        assert_eq!(pacing.deviating_frames, 1);
+        // The mean is 0.02
+        assert_close(
+            pacing.variance,
+            (3.0 * 0.004f32.powi(2) + 0.012f32.powi(2)) / 4.0,
+        );
+    }
+
+    #[test]
+    fn stable_frames() {
+        let pacing = FramePacing::new(&PacingConfig::default(), &[0.016; 8]);
+        assert_close(pacing.target_interval, 0.016);
+        assert_eq!(pacing.average_delta, 0.0);
+        assert_eq!(pacing.max_delta, 0.0);
+        assert_eq!(pacing.deviating_frames, 0);
+        assert_close(pacing.variance, 0.0);
+    }
+
+    #[test]
+    fn zero_target_interval_uses_the_average() {
+        let config = PacingConfig {
+            target_interval: Some(0.0),
+            tolerance: 0.1,
+        };
+        let pacing = FramePacing::new(&config, &[0.01, 0.03]);
+        assert_close(pacing.target_interval, 0.02);
+        assert_eq!(pacing.deviating_frames, 2);
+        assert_close(pacing.average_delta, 0.02);
+    }
+
+    #[test]
+    fn empty_and_single_frame() {
+        let pacing = FramePacing::new(&PacingConfig::default(), &[]);
+        assert_eq!(pacing.target_interval, 0.0);
+        assert_eq!(pacing.deviating_frames, 0);
+
+        let pacing = FramePacing::new(&PacingConfig::default(), &[0.016]);
+        assert_eq!(pacing.average_delta, 0.0);
+        assert_eq!(pacing.deviating_frames, 0);
+    }
+
+    #[test]
+    fn only_recorded_frames_are_measured() {
+        // The history only holds the frames recorded so far, unlike the zero filled start of the
+        // buffers uploaded to the gpu
+        let mut history = FrameHistory::default();
+        for frametime in [0.016, 0.016, 0.016] {
+            let sample = FrameSample {
+                frametime,
+                ..default()
+            };
+            history.push(sample, false);
+        }
+        let frametimes: Vec<f32> = history.frametimes().collect();
+        let pacing = FramePacing::new(&PacingConfig::default(), &frametimes);
+        assert_close(pacing.target_interval, 0.016);
+        assert_eq!(pacing.max_delta, 0.0);
+        assert_eq!(pacing.deviating_frames, 0);
+    }
+}
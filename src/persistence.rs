@@ -0,0 +1,230 @@
+use std::{fs, io, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{OverlayConfig, OverlayState};
+
+/// The name of the file saved in [`crate::OverlayPlugin::persistence_dir`]
+const PERSISTED_STATE_FILE: &str = "overlay_state.ron";
+
+/// The state of the overlay changed at runtime that is restored in the next sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PersistedState {
+    enabled: bool,
+    compact: bool,
+    ui_scale: f32,
+}
+
+impl PersistedState {
+    fn new(config: &OverlayConfig, state: &OverlayState) -> Self {
+        Self {
+            enabled: state.enabled,
+            compact: config.compact,
+            ui_scale: config.ui_scale,
+        }
+    }
+
+    fn apply(&self, config: &mut OverlayConfig, state: &mut OverlayState) {
+        state.enabled = self.enabled;
+        self.apply_config(config);
+    }
+
+    fn apply_config(&self, config: &mut OverlayConfig) {
+        config.compact = self.compact;
+        // The file can be edited by hand
+        if OverlayConfig::is_valid_ui_scale(self.ui_scale) {
+            config.ui_scale = self.ui_scale;
+        } else {
+            warn!(
+                "Ignoring the persisted ui_scale {} of the overlay, it must be positive",
+                self.ui_scale
+            );
+        }
+    }
+}
+
+/// Where the state is persisted and the last state that was saved
+#[derive(Resource)]
+pub(crate) struct OverlayPersistence {
+    path: PathBuf,
+    saved: Option<PersistedState>,
+}
+
+impl OverlayPersistence {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            path: dir.join(PERSISTED_STATE_FILE),
+            saved: None,
+        }
+    }
+
+    /// Applies the fields of the config that were restored or saved last
+    pub(crate) fn restore_config(&self, config: &mut OverlayConfig) {
+        if let Some(saved) = &self.saved {
+            saved.apply_config(config);
+        }
+    }
+}
+
+pub(crate) fn restore_state(
+    mut persistence: ResMut<OverlayPersistence>,
+    mut config: ResMut<OverlayConfig>,
+    mut state: ResMut<OverlayState>,
+) {
+    match fs::read_to_string(&persistence.path) {
+        Ok(text) => match ron::from_str::<PersistedState>(&text) {
+            Ok(persisted) => persisted.apply(&mut config, &mut state),
+            Err(err) => warn!("Failed to parse {}: {err}", persistence.path.display()),
+        },
+        // Nothing was saved yet
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => warn!("Failed to read {}: {err}", persistence.path.display()),
+    }
+    // Only save the state once it changes
+    persistence.saved = Some(PersistedState::new(&config, &state));
+}
+
+/// Saves the state every time it changes
+pub(crate) fn save_state(
+    mut persistence: ResMut<OverlayPersistence>,
+    config: Res<OverlayConfig>,
+    state: Res<OverlayState>,
+) {
+    if !config.is_changed() && !state.is_changed() {
+        return;
+    }
+    let current = PersistedState::new(&config, &state);
+    if persistence.saved.as_ref() == Some(&current) {
+        return;
+    }
+
+    let result = ron::ser::to_string_pretty(&current, default())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        .and_then(|text| {
+            if let Some(dir) = persistence.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&persistence.path, text)
+        });
+    if let Err(err) = result {
+        warn!("Failed to save {}: {err}", persistence.path.display());
+    }
+    // Don't retry every frame when the file can't be written
+    persistence.saved = Some(current);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy::ecs::schedule::SystemStage;
+
+    use super::*;
+
+    /// A directory unique to the test, removed when dropped
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("overlay_persistence_{name}_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+
+        fn file(&self) -> PathBuf {
+            self.0.join(PERSISTED_STATE_FILE)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn restored_world(dir: &Path) -> World {
+        let mut world = World::new();
+        world.init_resource::<OverlayConfig>();
+        world.init_resource::<OverlayState>();
+        world.insert_resource(OverlayPersistence::new(dir.to_path_buf()));
+        SystemStage::single(restore_state).run(&mut world);
+        world
+    }
+
+    fn save(world: &mut World) {
+        SystemStage::single(save_state).run(world);
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = TestDir::new("round_trip");
+        let mut world = restored_world(&dir.0);
+        world.resource_mut::<OverlayState>().enabled = false;
+        world.resource_mut::<OverlayConfig>().compact = true;
+        world.resource_mut::<OverlayConfig>().ui_scale = 1.5;
+        save(&mut world);
+
+        let world = restored_world(&dir.0);
+        assert!(!world.resource::<OverlayState>().enabled);
+        assert!(world.resource::<OverlayConfig>().compact);
+        assert_eq!(world.resource::<OverlayConfig>().ui_scale, 1.5);
+    }
+
+    #[test]
+    fn unchanged_state_is_not_saved() {
+        let dir = TestDir::new("unchanged");
+        let mut world = restored_world(&dir.0);
+        save(&mut world);
+        assert!(!dir.file().exists());
+    }
+
+    #[test]
+    fn invalid_ui_scale_is_ignored() {
+        let dir = TestDir::new("invalid_ui_scale");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(dir.file(), "(enabled: false, compact: true, ui_scale: 0.0)").unwrap();
+
+        let world = restored_world(&dir.0);
+        assert!(!world.resource::<OverlayState>().enabled);
+        assert!(world.resource::<OverlayConfig>().compact);
+        assert_eq!(world.resource::<OverlayConfig>().ui_scale, 1.0);
+    }
+
+    #[test]
+    fn invalid_file_is_ignored() {
+        let dir = TestDir::new("invalid_file");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(dir.file(), "not ron").unwrap();
+
+        let mut world = restored_world(&dir.0);
+        assert!(world.resource::<OverlayState>().enabled);
+        assert!(!world.resource::<OverlayConfig>().compact);
+        // The file is only overwritten once the state changes
+        save(&mut world);
+        assert_eq!(fs::read_to_string(dir.file()).unwrap(), "not ron");
+    }
+
+    #[test]
+    fn restored_state_takes_precedence_over_the_settings() {
+        let dir = TestDir::new("precedence");
+        let mut world = restored_world(&dir.0);
+        world.resource_mut::<OverlayConfig>().ui_scale = 2.0;
+        save(&mut world);
+
+        let world = restored_world(&dir.0);
+        let mut config = world.resource::<OverlayConfig>().clone();
+        crate::OverlaySettings {
+            ui_scale: Some(0.5),
+            compact: Some(true),
+            ..default()
+        }
+        .apply(&mut config);
+        world
+            .resource::<OverlayPersistence>()
+            .restore_config(&mut config);
+        assert_eq!(config.ui_scale, 2.0);
+        assert!(!config.compact);
+    }
+}
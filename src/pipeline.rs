@@ -3,23 +3,28 @@ use bevy::{
     render::{
         render_resource::{
             BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
-            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BlendState,
-            BufferBindingType, ColorTargetState, ColorWrites, FragmentState, MultisampleState,
-            PrimitiveState, RenderPipelineDescriptor, SamplerBindingType, ShaderStages, ShaderType,
-            TextureSampleType, TextureViewDimension, VertexState,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            BlendState, BufferBindingType, ColorTargetState, ColorWrites, FragmentState,
+            MultisampleState, PrimitiveState, RenderPipelineDescriptor, SamplerBindingType,
+            ShaderStages, ShaderType, TextureSampleType, TextureViewDimension, VertexState,
         },
         renderer::RenderDevice,
         texture::BevyDefault,
     },
 };
 
-use crate::{Frametimes, OverlayBindGroups, OverlayConfigUniform, OverlayDataUniform};
+use crate::{
+    font::GlyphAdvances, text::OverlayText, view::OverlayViewUniform, Frametimes,
+    GraphSeriesValues, Histogram, OverlayBindGroups, OverlayConfigUniform, OverlayDataUniform,
+};
 
 #[derive(Clone, Resource)]
 pub struct OverlayPipeline {
     pub shader: Handle<Shader>,
     pub layout: BindGroupLayout,
     pub bind_group: BindGroup,
+    /// The layout of the bind group of the values that depend on the view, bound with dynamic offsets
+    pub view_layout: BindGroupLayout,
 }
 
 impl FromWorld for OverlayPipeline {
@@ -30,9 +35,11 @@ impl FromWorld for OverlayPipeline {
 
         let layout = OverlayPipeline::layout(render_device);
         let bind_group = OverlayPipeline::create_bind_group(render_device, &layout, buffer);
+        let view_layout = OverlayPipeline::view_layout(render_device);
 
         OverlayPipeline {
             layout,
+            view_layout,
             shader: asset_server.load("shaders/frametime_display.wgsl"),
             bind_group,
         }
@@ -77,6 +84,18 @@ impl OverlayPipeline {
                     binding: 4,
                     resource: buffer.font_image_sampler.get_binding(),
                 },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: buffer.series_buffer.binding().unwrap(),
+                },
+                BindGroupEntry {
+                    binding: 6,
+                    resource: buffer.histogram_buffer.binding().unwrap(),
+                },
+                BindGroupEntry {
+                    binding: 7,
+                    resource: buffer.glyph_advances_buffer.binding().unwrap(),
+                },
             ],
             layout,
         })
@@ -131,15 +150,95 @@ impl OverlayPipeline {
                     ty: BindingType::Sampler(SamplerBindingType::Filtering),
                     visibility: ShaderStages::FRAGMENT,
                 },
+                BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GraphSeriesValues::min_size()),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(Histogram::min_size()),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GlyphAdvances::min_size()),
+                    },
+                    count: None,
+                },
             ],
             label: Some("overlay_bind_group_layout"),
         })
     }
 
+    pub fn create_view_bind_group(
+        &self,
+        render_device: &RenderDevice,
+        uniforms: BindingResource,
+        texts: BindingResource,
+    ) -> BindGroup {
+        render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("overlay view bind group"),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms,
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: texts,
+                },
+            ],
+            layout: &self.view_layout,
+        })
+    }
+
+    fn view_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(OverlayViewUniform::min_size()),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(OverlayText::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+            label: Some("overlay_view_bind_group_layout"),
+        })
+    }
+
     pub fn descriptor(&self) -> RenderPipelineDescriptor {
         RenderPipelineDescriptor {
             label: Some("Overlay Pipeline".into()),
-            layout: Some(vec![self.layout.clone()]),
+            layout: Some(vec![self.layout.clone(), self.view_layout.clone()]),
             vertex: VertexState {
                 shader: self.shader.clone(),
                 shader_defs: vec![],
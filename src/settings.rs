@@ -0,0 +1,270 @@
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, Error, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use ron::extensions::Extensions;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    persistence::OverlayPersistence, BarWidth, BudgetLine, ColorPalette, GraphScale, GraphStyle,
+    NumberFormat, OverlayConfig, OverlayRow, OverlayTheme, FRAMETIME_BUFFER_LEN,
+};
+
+/// Overrides of the [`OverlayConfig`] loaded from a `.overlay.ron` or `.overlay.toml` file
+///
+/// Every field overrides the field of the [`OverlayConfig`] with the same name, the missing fields
+/// keep their current value. The settings are applied again every time the file is modified when
+/// the [`AssetServer`] watches for changes.
+///
+/// ```ron
+/// (
+///     dts: (0.004, 0.016, 0.033, 0.066),
+///     palette: Viridis,
+///     rows: [Fps, FrameTime, Stats(Max), Graph],
+///     compact_key: F8,
+///     theme: (padding: 4.0, corner_radius: 4.0),
+/// )
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5b1a7c3e-2f4d-4a8e-9c61-0d8e3f7b2a94"]
+#[serde(deny_unknown_fields)]
+pub struct OverlaySettings {
+    /// The delta times in seconds where the colors change
+    pub dts: Option<[f32; 4]>,
+    /// Sets the colors to one of the presets, [`OverlaySettings::colors`] takes priority over it
+    pub palette: Option<ColorPalette>,
+    pub colors: Option<[Color; 4]>,
+    pub hatch_threshold: Option<f32>,
+    pub hitch_threshold: Option<f32>,
+    pub threshold_text_colors: Option<bool>,
+    pub budget_lines: Option<Vec<BudgetLine>>,
+    pub buffer_len: Option<usize>,
+    pub scale: Option<GraphScale>,
+    pub bar_width: Option<BarWidth>,
+    pub graph_style: Option<GraphStyle>,
+    pub antialiasing: Option<bool>,
+    pub rows: Option<Vec<OverlayRow>>,
+    pub compact: Option<bool>,
+    pub ui_scale: Option<f32>,
+    pub freeze_key: Option<KeyCode>,
+    pub compact_key: Option<KeyCode>,
+    /// The fields missing from the theme use the values of [`OverlayTheme::dark`]
+    pub theme: Option<OverlayTheme>,
+    pub number_format: Option<NumberFormat>,
+}
+
+impl OverlaySettings {
+    /// Parses the settings from TOML or RON
+    pub(crate) fn parse(bytes: &[u8], is_toml: bool) -> Result<Self, Error> {
+        let settings = if is_toml {
+            toml::from_slice(bytes)?
+        } else {
+            // The fields can be written without wrapping them in `Some`
+            ron::Options::default()
+                .with_default_extension(Extensions::IMPLICIT_SOME)
+                .from_bytes(bytes)?
+        };
+        Ok(settings)
+    }
+
+    /// Overrides the fields of the config that are set
+    pub fn apply(&self, config: &mut OverlayConfig) {
+        if let Some(dts) = self.dts {
+            config.dts = Vec4::from_array(dts);
+        }
+        if let Some(palette) = self.palette {
+            config.colors = palette.colors();
+        }
+        if let Some(colors) = self.colors {
+            config.colors = colors;
+        }
+        if let Some(hatch_threshold) = self.hatch_threshold {
+            config.hatch_threshold = Some(hatch_threshold);
+        }
+        if let Some(hitch_threshold) = self.hitch_threshold {
+            config.hitch_threshold = hitch_threshold;
+        }
+        if let Some(threshold_text_colors) = self.threshold_text_colors {
+            config.threshold_text_colors = threshold_text_colors;
+        }
+        if let Some(budget_lines) = &self.budget_lines {
+            config.budget_lines = budget_lines.clone();
+        }
+        if let Some(buffer_len) = self.buffer_len {
+            config.buffer_len = buffer_len.clamp(1, FRAMETIME_BUFFER_LEN);
+        }
+        if let Some(scale) = self.scale {
+            config.scale = scale;
+        }
+        if let Some(bar_width) = self.bar_width {
+            config.bar_width = bar_width;
+        }
+        if let Some(graph_style) = self.graph_style {
+            config.graph_style = graph_style;
+        }
+        if let Some(antialiasing) = self.antialiasing {
+            config.antialiasing = antialiasing;
+        }
+        if let Some(rows) = &self.rows {
+            config.rows = rows.clone();
+        }
+        if let Some(compact) = self.compact {
+            config.compact = compact;
+        }
+        if let Some(ui_scale) = self.ui_scale {
+            if OverlayConfig::is_valid_ui_scale(ui_scale) {
+                config.ui_scale = ui_scale;
+            } else {
+                warn!(
+                    "Ignoring the ui_scale {ui_scale} of the overlay settings, it must be positive"
+                );
+            }
+        }
+        if let Some(freeze_key) = self.freeze_key {
+            config.freeze_key = Some(freeze_key);
+        }
+        if let Some(compact_key) = self.compact_key {
+            config.compact_key = Some(compact_key);
+        }
+        if let Some(theme) = &self.theme {
+            config.theme = theme.clone();
+        }
+        if let Some(number_format) = &self.number_format {
+            config.number_format = number_format.clone();
+        }
+    }
+}
+
+/// Loads the [`OverlaySettings`] from RON or TOML depending on the extension of the file
+#[derive(Default)]
+pub struct OverlaySettingsLoader;
+
+impl AssetLoader for OverlaySettingsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let is_toml = load_context.path().extension() == Some("toml".as_ref());
+            let settings = OverlaySettings::parse(bytes, is_toml)?;
+            load_context.set_default_asset(LoadedAsset::new(settings));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["overlay.ron", "overlay.toml"]
+    }
+}
+
+/// Applies the [`OverlayConfig::settings`] once loaded and every time they are modified
+pub(crate) fn apply_settings(
+    mut config: ResMut<OverlayConfig>,
+    settings: Res<Assets<OverlaySettings>>,
+    mut settings_events: EventReader<AssetEvent<OverlaySettings>>,
+    mut applied_settings: Local<Option<Handle<OverlaySettings>>>,
+    persistence: Option<Res<OverlayPersistence>>,
+) {
+    let handle = match &config.settings {
+        Some(handle) => handle.clone(),
+        None => {
+            *applied_settings = None;
+            return;
+        }
+    };
+
+    let settings_modified = settings_events.iter().any(|event| match event {
+        AssetEvent::Created { handle: modified } | AssetEvent::Modified { handle: modified } => {
+            *modified == handle
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    if !settings_modified && applied_settings.as_ref() == Some(&handle) {
+        return;
+    }
+
+    if let Some(settings) = settings.get(&handle) {
+        settings.apply(&mut config);
+        // The state restored at startup takes precedence over the settings when they are first loaded
+        if applied_settings.is_none() {
+            if let Some(persistence) = &persistence {
+                persistence.restore_config(&mut config);
+            }
+        }
+        *applied_settings = Some(handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_ron() {
+        let settings =
+            OverlaySettings::parse(include_bytes!("../assets/example.overlay.ron"), false).unwrap();
+        assert_eq!(settings.palette, Some(ColorPalette::Default));
+        assert_eq!(settings.hatch_threshold, Some(0.033333));
+        assert_eq!(settings.freeze_key, Some(KeyCode::F9));
+        assert_eq!(settings.rows.map(|rows| rows.len()), Some(5));
+        assert_eq!(settings.theme.map(|theme| theme.padding), Some(0.0));
+        assert!(settings.buffer_len.is_none());
+    }
+
+    #[test]
+    fn toml() {
+        let toml = r#"
+            dts = [0.004, 0.016, 0.033, 0.066]
+            palette = "Viridis"
+            buffer_len = 32
+            rows = ["Fps", { Stats = "Max" }, "Graph"]
+            compact_key = "F8"
+
+            [theme]
+            padding = 4.0
+            corner_radius = 4.0
+
+            [number_format.units]
+            fps = " FPS"
+        "#;
+        let settings = OverlaySettings::parse(toml.as_bytes(), true).unwrap();
+        assert_eq!(settings.dts, Some([0.004, 0.016, 0.033, 0.066]));
+        assert_eq!(settings.palette, Some(ColorPalette::Viridis));
+        assert_eq!(settings.buffer_len, Some(32));
+        assert_eq!(
+            settings.rows,
+            Some(vec![
+                OverlayRow::Fps,
+                OverlayRow::Stats(crate::FrameStat::Max),
+                OverlayRow::Graph
+            ])
+        );
+        assert_eq!(settings.compact_key, Some(KeyCode::F8));
+        assert_eq!(settings.theme.map(|theme| theme.corner_radius), Some(4.0));
+        let units = settings.number_format.unwrap().units;
+        assert_eq!(units.fps, " FPS");
+        assert_eq!(units.milliseconds, "ms");
+    }
+
+    #[test]
+    fn apply_keeps_valid_values() {
+        let mut config = OverlayConfig::default();
+        OverlaySettings {
+            buffer_len: Some(0),
+            ui_scale: Some(0.0),
+            ..default()
+        }
+        .apply(&mut config);
+        assert_eq!(config.buffer_len, 1);
+        assert_eq!(config.ui_scale, 1.0);
+
+        OverlaySettings {
+            buffer_len: Some(FRAMETIME_BUFFER_LEN + 1),
+            ..default()
+        }
+        .apply(&mut config);
+        assert_eq!(config.buffer_len, FRAMETIME_BUFFER_LEN);
+    }
+}
@@ -0,0 +1,218 @@
+use bevy::render::render_resource::ShaderType;
+
+use crate::view::OverlayView;
+use crate::{
+    format::glyphs, CustomRows, FrameStat, Frametimes, NumberFormat, OverlayConfig,
+    OverlayDataUniform, OverlayRow, FRAMETIME_BUFFER_LEN, MAX_BUDGET_LINES, MAX_OVERLAY_ROWS,
+};
+
+/// The maximum amount of rows of text of the overlay, the rows and the 2 rows of the hover tooltip
+pub const MAX_TEXT_ROWS: usize = MAX_OVERLAY_ROWS + 2;
+/// The maximum amount of glyphs in a row of text, the rest of the row is cut off
+pub const MAX_ROW_GLYPHS: usize = 32;
+
+/// A line of text as indices of glyphs of the font atlas
+#[derive(Debug, Clone, Copy, ShaderType)]
+pub struct TextLine {
+    pub len: u32,
+    /// Colors the text like a frame of this delta time in the graph when positive,
+    /// otherwise the text uses [`crate::OverlayTheme::text_color`]
+    pub color_dt: f32,
+    pub glyphs: [u32; MAX_ROW_GLYPHS],
+}
+
+impl Default for TextLine {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            color_dt: 0.0,
+            glyphs: [0; MAX_ROW_GLYPHS],
+        }
+    }
+}
+
+impl TextLine {
+    pub fn new(text: &str) -> Self {
+        let mut line = Self::default();
+        for (glyph, code) in line.glyphs.iter_mut().zip(glyphs(text)) {
+            *glyph = code;
+            line.len += 1;
+        }
+        line
+    }
+
+    pub fn with_color_dt(mut self, dt: f32) -> Self {
+        self.color_dt = dt;
+        self
+    }
+}
+
+/// The text drawn by the overlay, formatted on the cpu for each view
+#[derive(Debug, Clone, Default, ShaderType)]
+pub struct OverlayText {
+    /// The text of each row, empty for the rows of the graphs, followed by the hover tooltip
+    pub rows: [TextLine; MAX_TEXT_ROWS],
+    /// The label of each budget line
+    pub labels: [TextLine; MAX_BUDGET_LINES],
+}
+
+impl OverlayText {
+    /// Formats the text of the overlay drawn on the given view
+    pub(crate) fn new(
+        config: &OverlayConfig,
+        data: &OverlayDataUniform,
+        frametimes: &Frametimes,
+        custom_rows: &CustomRows,
+        view: &OverlayView,
+    ) -> Self {
+        let format = &config.number_format;
+        let recorded = || frametimes.recorded(config.graph_start());
+        // The buffer is filled from the end
+        let latest_dt = frametimes.values[FRAMETIME_BUFFER_LEN - 1];
+
+        if config.compact {
+            // The fps are rounded and the frametime only keeps a decimal to fit on a single short line
+            let fps_format = NumberFormat {
+                decimals: 0,
+                ..format.clone()
+            };
+            let frametime_format = NumberFormat {
+                decimals: 1,
+                ..format.clone()
+            };
+            let row = format!(
+                "{} | {}",
+                fps_format.format_fps(data.fps),
+                frametime_format.format_ms(latest_dt),
+            );
+            let mut text = OverlayText::default();
+            text.rows[0] = TextLine::new(&row).with_color_dt(latest_dt);
+            return text;
+        }
+
+        // The delta time coloring the row, 0 keeps the color of the theme
+        let color_dt = |dt: f32| {
+            if config.threshold_text_colors {
+                dt
+            } else {
+                0.0
+            }
+        };
+        let mut rows: Vec<TextLine> = config
+            .shown_rows()
+            .iter()
+            .map(|row| match row {
+                OverlayRow::Fps => {
+                    let dt = if data.fps > 0.0 { 1.0 / data.fps } else { 0.0 };
+                    TextLine::new(&format.format_fps(data.fps)).with_color_dt(color_dt(dt))
+                }
+                OverlayRow::FrameTime => {
+                    TextLine::new(&format.format_ms(latest_dt)).with_color_dt(color_dt(latest_dt))
+                }
+                OverlayRow::FrameCount => TextLine::new(&format!(
+                    "Frame: {}",
+                    format.format_integer(data.frame_count as u64, "")
+                )),
+                OverlayRow::Resolution => TextLine::new(&format!(
+                    "{}x{} ({}%)",
+                    format.format_integer(view.resolution.x as u64, ""),
+                    format.format_integer(view.resolution.y as u64, ""),
+                    (view.scale_factor * 100.0).round(),
+                )),
+                OverlayRow::Stats(stat) => {
+                    let label = match stat {
+                        FrameStat::Average => "avg",
+                        FrameStat::Min => "min",
+                        FrameStat::Max => "max",
+                    };
+                    let dt = stat.compute(recorded()).unwrap_or_default();
+                    TextLine::new(&format!("{label}: {}", format.format_ms(dt)))
+                        .with_color_dt(color_dt(dt))
+                }
+                OverlayRow::Pacing => TextLine::new(&format!(
+                    "jit:{} dev:{}",
+                    format.format_ms(data.pacing_average_delta),
+                    format.format_integer(data.pacing_deviating_frames as u64, ""),
+                )),
+                OverlayRow::Custom(key) => TextLine::new(custom_rows.get(key).unwrap_or_default()),
+                OverlayRow::Graph | OverlayRow::PacingGraph | OverlayRow::Histogram => {
+                    TextLine::default()
+                }
+            })
+            .collect();
+        if view.hoverable && data.hovered_index >= 0 {
+            rows.push(TextLine::new(&format!(
+                "{} F:{}",
+                format.format_ms(data.hovered_frametime),
+                format.format_integer(data.hovered_frame as u64, ""),
+            )));
+            rows.push(TextLine::new(&format!(
+                "t:{}",
+                format.format_seconds(data.hovered_timestamp)
+            )));
+        }
+
+        let mut overlay_text = OverlayText::default();
+        for (line, row) in overlay_text.rows.iter_mut().zip(rows) {
+            *line = row;
+        }
+        for (label, budget_line) in overlay_text.labels.iter_mut().zip(&config.budget_lines) {
+            if budget_line.label {
+                *label = TextLine::new(&format.format_ms(budget_line.dt));
+            }
+        }
+        overlay_text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn view() -> OverlayView {
+        OverlayView {
+            resolution: UVec2::new(1280, 720),
+            scale_factor: 1.0,
+            hoverable: false,
+        }
+    }
+
+    #[test]
+    fn short_graph_uses_the_most_recent_frames() {
+        let config = OverlayConfig {
+            buffer_len: 4,
+            threshold_text_colors: true,
+            rows: vec![OverlayRow::FrameTime, OverlayRow::Stats(FrameStat::Max)],
+            ..default()
+        };
+        let mut frametimes = Frametimes::default();
+        // The oldest frames are the longest, they aren't part of the graph
+        for dt in [0.1, 0.09, 0.08, 0.01, 0.02, 0.04, 0.03] {
+            frametimes.push(dt);
+        }
+        let text = OverlayText::new(
+            &config,
+            &OverlayDataUniform::default(),
+            &frametimes,
+            &CustomRows::default(),
+            &view(),
+        );
+        assert_eq!(text.rows[0].color_dt, 0.03);
+        assert_eq!(text.rows[1].color_dt, 0.04);
+
+        let compact_config = OverlayConfig {
+            compact: true,
+            ..config
+        };
+        let text = OverlayText::new(
+            &compact_config,
+            &OverlayDataUniform::default(),
+            &frametimes,
+            &CustomRows::default(),
+            &view(),
+        );
+        assert_eq!(text.rows[0].color_dt, 0.03);
+    }
+}
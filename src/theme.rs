@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The look of the overlay around the graphs
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect, FromReflect)]
+#[serde(default)]
+pub struct OverlayTheme {
+    /// The color behind the text and the graphs, its alpha is the opacity of the overlay
+    pub background: Color,
+    pub text_color: Color,
+    /// Draws a shadow under the text, offset by a pixel to the bottom right
+    pub text_shadow: Option<Color>,
+    /// Draws an outline of a pixel around the text, only with [`crate::TextRendering::Sdf`]
+    pub text_outline: Option<Color>,
+    /// The space between the border of the overlay and its content in logical pixels
+    pub padding: f32,
+    /// The radius of the corners of the background in logical pixels
+    pub corner_radius: f32,
+}
+
+impl Default for OverlayTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl OverlayTheme {
+    /// White text on a translucent black background
+    pub fn dark() -> Self {
+        Self {
+            background: Color::rgba(0.0, 0.0, 0.0, 0.4),
+            text_color: Color::WHITE,
+            text_shadow: None,
+            text_outline: None,
+            padding: 0.0,
+            corner_radius: 0.0,
+        }
+    }
+
+    /// Dark text on a translucent white background
+    pub fn light() -> Self {
+        Self {
+            background: Color::rgba(1.0, 1.0, 1.0, 0.7),
+            text_color: Color::rgb(0.1, 0.1, 0.1),
+            text_shadow: None,
+            text_outline: None,
+            padding: 4.0,
+            corner_radius: 4.0,
+        }
+    }
+
+    /// Yellow text with a black shadow on an opaque black background
+    pub fn high_contrast() -> Self {
+        Self {
+            background: Color::BLACK,
+            text_color: Color::YELLOW,
+            text_shadow: Some(Color::BLACK),
+            text_outline: None,
+            padding: 4.0,
+            corner_radius: 0.0,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{ExtractedCamera, RenderTarget},
+        render_resource::{BindGroup, DynamicStorageBuffer, DynamicUniformBuffer, ShaderType},
+        renderer::{RenderDevice, RenderQueue},
+        Extract,
+    },
+    utils::HashMap,
+    window::WindowId,
+};
+
+use crate::{
+    pipeline::OverlayPipeline, text::OverlayText, CameraOverlay, CustomRows, Frametimes,
+    OverlayConfig, OverlayDataUniform, OverlayState,
+};
+
+/// The target of a view drawing the overlay
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OverlayView {
+    /// The physical size of the target
+    pub(crate) resolution: UVec2,
+    /// The scale factor of the window, 1 for an image
+    pub(crate) scale_factor: f32,
+    /// Only the overlay drawn on the primary window can be hovered
+    pub(crate) hoverable: bool,
+}
+
+/// The values of the shader that depend on the target of the view
+#[derive(Debug, Clone, ShaderType)]
+pub(crate) struct OverlayViewUniform {
+    /// The scale factor of the target multiplied by [`OverlayConfig::ui_scale`]
+    layout_scale: f32,
+    /// The index of the hovered frame in the frametimes buffer, -1 when the view isn't hovered
+    hovered_index: i32,
+}
+
+/// The offsets of a view in the [`OverlayViewBuffers`]
+#[derive(Component)]
+pub(crate) struct OverlayViewOffsets {
+    pub(crate) uniform: u32,
+    pub(crate) text: u32,
+}
+
+/// The uniform and the text of every view drawing the overlay, rebuilt every frame
+#[derive(Default, Resource)]
+pub(crate) struct OverlayViewBuffers {
+    uniforms: DynamicUniformBuffer<OverlayViewUniform>,
+    texts: DynamicStorageBuffer<OverlayText>,
+    pub(crate) bind_group: Option<BindGroup>,
+}
+
+/// The scale factor of each window, the render world only knows their physical size
+#[derive(Default, Resource)]
+pub(crate) struct WindowScaleFactors(HashMap<WindowId, f32>);
+
+pub(crate) fn extract_window_scale_factors(
+    windows: Extract<Res<Windows>>,
+    mut scale_factors: ResMut<WindowScaleFactors>,
+) {
+    scale_factors.0.clear();
+    scale_factors.0.extend(
+        windows
+            .iter()
+            .map(|window| (window.id(), window.scale_factor() as f32)),
+    );
+}
+
+pub(crate) fn prepare_overlay_views(
+    mut commands: Commands,
+    config: Res<OverlayConfig>,
+    data: Res<OverlayDataUniform>,
+    frametimes: Res<Frametimes>,
+    custom_rows: Res<CustomRows>,
+    state: Res<OverlayState>,
+    scale_factors: Res<WindowScaleFactors>,
+    views: Query<(Entity, &ExtractedCamera), With<CameraOverlay>>,
+    pipeline: Res<OverlayPipeline>,
+    mut buffers: ResMut<OverlayViewBuffers>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    buffers.uniforms.clear();
+    buffers.texts.clear();
+    // The node doesn't draw anything
+    if !state.enabled {
+        buffers.bind_group = None;
+        return;
+    }
+
+    for (entity, camera) in &views {
+        let view = match &camera.target {
+            RenderTarget::Window(id) => OverlayView {
+                resolution: camera.physical_target_size.unwrap_or_default(),
+                scale_factor: scale_factors.0.get(id).copied().unwrap_or(1.0),
+                hoverable: *id == WindowId::primary(),
+            },
+            // An image has no scale factor, the overlay is only scaled by the ui scale
+            RenderTarget::Image(_) => OverlayView {
+                resolution: camera.physical_target_size.unwrap_or_default(),
+                scale_factor: 1.0,
+                hoverable: false,
+            },
+        };
+
+        let uniform = OverlayViewUniform {
+            layout_scale: config.layout_scale(view.scale_factor),
+            hovered_index: if view.hoverable {
+                data.hovered_index
+            } else {
+                -1
+            },
+        };
+        let text = OverlayText::new(&config, &data, &frametimes, &custom_rows, &view);
+        commands.entity(entity).insert(OverlayViewOffsets {
+            uniform: buffers.uniforms.push(uniform),
+            text: buffers.texts.push(text),
+        });
+    }
+
+    if buffers.uniforms.is_empty() {
+        buffers.bind_group = None;
+        return;
+    }
+    buffers.uniforms.write_buffer(&render_device, &render_queue);
+    buffers.texts.write_buffer(&render_device, &render_queue);
+    buffers.bind_group = match (buffers.uniforms.binding(), buffers.texts.binding()) {
+        (Some(uniforms), Some(texts)) => {
+            Some(pipeline.create_view_bind_group(&render_device, uniforms, texts))
+        }
+        _ => None,
+    };
+}